@@ -0,0 +1,292 @@
+//! Future-based async I/O on raw streams.
+//!
+//! [`InputStream::write_async`] and [`OutputStream::read_async`] move the
+//! buffer into an [`AsyncTransfer`] future, which resolves to
+//! `(buffer, result)` once HailoRT invokes the completion callback.  The
+//! futures are runtime-agnostic: completion wakes whatever executor polled
+//! them last.
+//!
+//! Streams must be configured with `HAILO_STREAM_FLAGS_ASYNC` (see
+//! [`ConfigureParamsBuilder::stream_flags`](crate::ConfigureParamsBuilder::stream_flags)).
+//! Dropping a future does not cancel the transfer; the buffer is freed when
+//! the callback runs.  [`ActivatedNetworkGroup`](crate::ActivatedNetworkGroup)
+//! waits for every outstanding transfer when it is dropped.
+
+use std::ffi::c_void;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use crate::error::{HailoResult, check};
+use crate::ffi::{hailo_input_stream_write_async, hailo_output_stream_read_async};
+use crate::network_group::{InputStream, OutputStream};
+use crate::status::hailo_status;
+use crate::types::{
+    hailo_stream_read_async_completion_info_t, hailo_stream_write_async_completion_info_t,
+};
+
+/// Byte buffer whose storage does not move when the value itself is moved.
+///
+/// # Safety
+/// The slices returned by `as_ref` / `as_mut` must keep the same address and
+/// length while the value is moved and not otherwise accessed.
+pub unsafe trait StableBuffer: AsRef<[u8]> + AsMut<[u8]> + Send + 'static {}
+
+unsafe impl StableBuffer for Vec<u8> {}
+unsafe impl StableBuffer for Box<[u8]> {}
+
+// ---------------------------------------------------------------------------
+// Outstanding-operation tracking
+// ---------------------------------------------------------------------------
+
+/// Count of issued transfers whose callbacks have not run yet.
+#[derive(Debug, Default)]
+pub(crate) struct PendingOps {
+    count: Mutex<usize>,
+    idle: Condvar,
+}
+
+impl PendingOps {
+    fn begin(&self) {
+        *self.count.lock().unwrap_or_else(PoisonError::into_inner) += 1;
+    }
+
+    fn end(&self) {
+        let mut count = self.count.lock().unwrap_or_else(PoisonError::into_inner);
+        *count -= 1;
+        if *count == 0 {
+            self.idle.notify_all();
+        }
+    }
+
+    /// Blocks until every issued transfer has completed.
+    pub(crate) fn wait_idle(&self) {
+        let count = self.count.lock().unwrap_or_else(PoisonError::into_inner);
+        let _idle = self
+            .idle
+            .wait_while(count, |count| *count > 0)
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Future
+// ---------------------------------------------------------------------------
+
+struct OpState<B> {
+    buffer: Option<B>,
+    status: Option<hailo_status>,
+    waker: Option<Waker>,
+}
+
+/// Shared between the future and the C callback (via `opaque`).
+struct Op<B> {
+    state: Mutex<OpState<B>>,
+    pending: Arc<PendingOps>,
+}
+
+impl<B> Op<B> {
+    fn new(buffer: B, pending: &Arc<PendingOps>) -> Arc<Self> {
+        pending.begin();
+        Arc::new(Self {
+            state: Mutex::new(OpState {
+                buffer: Some(buffer),
+                status: None,
+                waker: None,
+            }),
+            pending: Arc::clone(pending),
+        })
+    }
+
+    /// Runs inside the C completion callback, where a panic would abort the
+    /// process, so poisoned locks here and in [`PendingOps`] are recovered
+    /// instead of unwrapped.
+    fn complete(&self, status: hailo_status) {
+        let waker = {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            state.status = Some(status);
+            state.waker.take()
+        };
+        self.pending.end();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Pending async transfer.  Resolves to the buffer and the transfer result.
+///
+/// # Panics
+/// Polling again after it returned `Ready` panics.
+#[must_use = "the buffer is only returned by awaiting the transfer"]
+pub struct AsyncTransfer<B> {
+    op: Arc<Op<B>>,
+}
+
+impl<B> Future for AsyncTransfer<B> {
+    type Output = (B, HailoResult<()>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.op.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match state.status {
+            Some(status) => {
+                let buffer = state
+                    .buffer
+                    .take()
+                    .expect("AsyncTransfer polled after completion");
+                Poll::Ready((buffer, check(status)))
+            }
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<B> std::fmt::Debug for AsyncTransfer<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let done = self
+            .op
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .status
+            .is_some();
+        f.debug_struct("AsyncTransfer")
+            .field("done", &done)
+            .finish()
+    }
+}
+
+/// Issues a transfer with `issue(opaque)`.  On synchronous failure the
+/// callback never runs, so the op is completed here instead.
+fn start<B>(op: Arc<Op<B>>, issue: impl FnOnce(*mut c_void) -> hailo_status) -> AsyncTransfer<B> {
+    let opaque = Arc::into_raw(Arc::clone(&op)) as *mut c_void;
+    let status = issue(opaque);
+    if check(status).is_err() {
+        // SAFETY: the runtime rejected the call and will not use `opaque`.
+        let op = unsafe { Arc::from_raw(opaque as *const Op<B>) };
+        op.complete(status);
+    }
+    AsyncTransfer { op }
+}
+
+unsafe extern "C" fn write_done<B>(info: *const hailo_stream_write_async_completion_info_t) {
+    let info = unsafe { &*info };
+    let op = unsafe { Arc::from_raw(info.opaque as *const Op<B>) };
+    op.complete(info.status);
+}
+
+unsafe extern "C" fn read_done<B>(info: *const hailo_stream_read_async_completion_info_t) {
+    let info = unsafe { &*info };
+    let op = unsafe { Arc::from_raw(info.opaque as *const Op<B>) };
+    op.complete(info.status);
+}
+
+impl InputStream<'_> {
+    /// Queues one frame (device layout) for writing.
+    pub fn write_async<B: StableBuffer>(&self, buffer: B) -> AsyncTransfer<B> {
+        let frame = buffer.as_ref();
+        let (ptr, len) = (frame.as_ptr(), frame.len());
+        let op = Op::new(buffer, &self.pending);
+        start(op, |opaque| unsafe {
+            hailo_input_stream_write_async(
+                self.as_raw(),
+                ptr.cast::<c_void>(),
+                len,
+                write_done::<B>,
+                opaque,
+            )
+        })
+    }
+}
+
+impl OutputStream<'_> {
+    /// Queues `buffer` to receive one frame (device layout).
+    pub fn read_async<B: StableBuffer>(&self, mut buffer: B) -> AsyncTransfer<B> {
+        let frame = buffer.as_mut();
+        let (ptr, len) = (frame.as_mut_ptr(), frame.len());
+        let op = Op::new(buffer, &self.pending);
+        start(op, |opaque| unsafe {
+            hailo_output_stream_read_async(
+                self.as_raw(),
+                ptr.cast::<c_void>(),
+                len,
+                read_done::<B>,
+                opaque,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::HailoError;
+    use crate::status::{HAILO_INVALID_OPERATION, HAILO_STREAM_ABORT, HAILO_SUCCESS};
+    use std::thread;
+
+    fn poll<F: Future + Unpin>(future: &mut F) -> Poll<F::Output> {
+        Pin::new(future).poll(&mut Context::from_waker(Waker::noop()))
+    }
+
+    #[test]
+    fn resolves_after_callback_with_buffer() {
+        let pending = Arc::new(PendingOps::default());
+        let mut transfer = start(Op::new(vec![7u8; 4], &pending), |opaque| {
+            let info = hailo_stream_write_async_completion_info_t {
+                status: HAILO_SUCCESS,
+                buffer_addr: std::ptr::null(),
+                buffer_size: 4,
+                opaque,
+            };
+            // Completes later, on another thread, like the runtime does.
+            let info = SendInfo(info);
+            thread::spawn(move || {
+                let info = info;
+                unsafe { write_done::<Vec<u8>>(&info.0) }
+            });
+            HAILO_SUCCESS
+        });
+        pending.wait_idle();
+        let Poll::Ready((buffer, result)) = poll(&mut transfer) else {
+            panic!("transfer should be complete");
+        };
+        assert_eq!(buffer, [7; 4]);
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn synchronous_failure_returns_buffer_immediately() {
+        let pending = Arc::new(PendingOps::default());
+        let mut transfer = start(Op::new(Box::<[u8]>::from([1u8, 2]), &pending), |_| {
+            HAILO_INVALID_OPERATION
+        });
+        pending.wait_idle();
+        let Poll::Ready((buffer, result)) = poll(&mut transfer) else {
+            panic!("failed transfer should resolve immediately");
+        };
+        assert_eq!(&*buffer, [1, 2]);
+        assert_eq!(result, Err(HailoError::InvalidOperation));
+    }
+
+    #[test]
+    fn stays_pending_until_completion() {
+        let pending = Arc::new(PendingOps::default());
+        let op = Op::new(vec![0u8; 1], &pending);
+        let mut transfer = AsyncTransfer {
+            op: Arc::clone(&op),
+        };
+        assert!(poll(&mut transfer).is_pending());
+        op.complete(HAILO_STREAM_ABORT);
+        let Poll::Ready((_, result)) = poll(&mut transfer) else {
+            panic!("transfer should be complete");
+        };
+        assert_eq!(result, Err(HailoError::StreamAbort));
+    }
+
+    struct SendInfo(hailo_stream_write_async_completion_info_t);
+    unsafe impl Send for SendInfo {}
+}
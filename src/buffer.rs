@@ -0,0 +1,576 @@
+//! Page-aligned frame buffers and a recycling [`BufferPool`].
+//!
+//! Async stream I/O and DMA mapping reject buffers that do not start on a
+//! page boundary with `HAILO_DATA_ALIGNMENT_FAILURE`, which a `Vec<u8>` does
+//! not guarantee.  [`FrameBuffer`] allocates whole, zero-filled pages and
+//! exposes exactly one frame; it implements [`StableBuffer`] for
+//! [`InputStream::write_async`](crate::InputStream::write_async) and
+//! [`OutputStream::read_async`](crate::OutputStream::read_async).
+//!
+//! [`BufferPool`] hands out [`PooledBuffer`]s that return to the pool when
+//! dropped.  Built with [`dma_map`](BufferPoolBuilder::dma_map), it maps each
+//! buffer it allocates into the device's DMA address space once, so transfers
+//! skip per-call mapping.  A mapping lasts as long as its buffer: the pool
+//! unmaps the buffers it holds when dropped, and a buffer still handed out at
+//! that point unmaps itself when it is dropped.  Pooled buffers borrow the
+//! device, so they are [`StableBuffer`]s only for a `'static` device.
+
+use std::alloc::{self, Layout};
+use std::ffi::c_void;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::os::raw::c_int;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::async_io::StableBuffer;
+use crate::device::Device;
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{
+    hailo_device_dma_map_buffer, hailo_device_dma_unmap_buffer, hailo_vdevice_dma_map_buffer,
+    hailo_vdevice_dma_unmap_buffer,
+};
+use crate::hef::StreamInfo;
+use crate::typed_enums::DmaBufferDirection;
+use crate::types::hailo_stream_info_t;
+use crate::vdevice::VDevice;
+use crate::vstream::{InputVStream, OutputVStream};
+
+/// Page size assumed when the host's cannot be queried.
+const FALLBACK_PAGE_SIZE: usize = 4096;
+
+/// Host page size, the alignment and size granularity of every
+/// [`FrameBuffer`].
+///
+/// Queried with `sysconf(_SC_PAGESIZE)` on Linux.  Elsewhere, or if the query
+/// fails, it is 4096, which is also the alignment HailoRT checks for.
+pub fn page_size() -> usize {
+    static PAGE_SIZE: OnceLock<usize> = OnceLock::new();
+    *PAGE_SIZE.get_or_init(|| {
+        query_page_size()
+            .filter(|size| size.is_power_of_two())
+            .unwrap_or(FALLBACK_PAGE_SIZE)
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn query_page_size() -> Option<usize> {
+    unsafe extern "C" {
+        fn sysconf(name: c_int) -> std::os::raw::c_long;
+    }
+    // Same value on every Linux architecture, in glibc and musl alike.
+    const _SC_PAGESIZE: c_int = 30;
+    // SAFETY: `sysconf` has no preconditions.
+    usize::try_from(unsafe { sysconf(_SC_PAGESIZE) }).ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn query_page_size() -> Option<usize> {
+    None
+}
+
+/// Something that knows how many bytes one frame takes.
+pub trait FrameSized {
+    fn frame_size(&self) -> usize;
+}
+
+impl FrameSized for StreamInfo {
+    /// `hw_frame_size`, the size raw stream transfers use.
+    fn frame_size(&self) -> usize {
+        self.hw_frame_size as usize
+    }
+}
+
+impl FrameSized for hailo_stream_info_t {
+    fn frame_size(&self) -> usize {
+        self.hw_frame_size as usize
+    }
+}
+
+impl FrameSized for InputVStream<'_> {
+    fn frame_size(&self) -> usize {
+        InputVStream::frame_size(self)
+    }
+}
+
+impl FrameSized for OutputVStream<'_> {
+    fn frame_size(&self) -> usize {
+        OutputVStream::frame_size(self)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FrameBuffer
+// ---------------------------------------------------------------------------
+
+/// Zero-initialised, page-aligned heap buffer holding one frame.
+///
+/// The allocation is rounded up to whole pages ([`capacity`](Self::capacity));
+/// the slice it derefs to is exactly the requested length.
+pub struct FrameBuffer {
+    ptr: NonNull<u8>,
+    len: usize,
+    layout: Layout,
+}
+
+// SAFETY: the buffer owns its allocation exclusively, like a `Box<[u8]>`.
+unsafe impl Send for FrameBuffer {}
+// SAFETY: shared access only hands out `&[u8]`.
+unsafe impl Sync for FrameBuffer {}
+
+impl FrameBuffer {
+    /// Allocates `len` bytes.  Fails with `InvalidArgument` for zero or
+    /// unrepresentable lengths and `OutOfHostMemory` if allocation fails.
+    pub fn new(len: usize) -> HailoResult<Self> {
+        let layout = Self::layout(len)?;
+        // SAFETY: `layout` has a non-zero size.
+        let ptr = NonNull::new(unsafe { alloc::alloc_zeroed(layout) })
+            .ok_or(HailoError::OutOfHostMemory)?;
+        Ok(Self { ptr, len, layout })
+    }
+
+    /// Whole-page layout holding `len` bytes.
+    fn layout(len: usize) -> HailoResult<Layout> {
+        if len == 0 {
+            return Err(HailoError::InvalidArgument);
+        }
+        let page = page_size();
+        let capacity = len
+            .checked_next_multiple_of(page)
+            .ok_or(HailoError::InvalidArgument)?;
+        Layout::from_size_align(capacity, page).map_err(|_| HailoError::InvalidArgument)
+    }
+
+    /// Allocates one frame of `source`, e.g. a [`StreamInfo`] or an
+    /// [`OutputVStream`].
+    pub fn for_frame(source: &impl FrameSized) -> HailoResult<Self> {
+        Self::new(source.frame_size())
+    }
+
+    /// Allocated bytes: the frame length rounded up to whole pages.
+    pub fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+}
+
+impl Deref for FrameBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: `ptr` is valid and initialised for `capacity >= len` bytes.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for FrameBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: as in `deref`, and `&mut self` makes the access unique.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl AsRef<[u8]> for FrameBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsMut<[u8]> for FrameBuffer {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+// SAFETY: the slice points into a heap allocation that moves with neither
+// the `FrameBuffer` nor its length.
+unsafe impl StableBuffer for FrameBuffer {}
+
+impl Drop for FrameBuffer {
+    fn drop(&mut self) {
+        // SAFETY: allocated in `new` with this layout.
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+    }
+}
+
+impl fmt::Debug for FrameBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameBuffer")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// DMA mapping
+// ---------------------------------------------------------------------------
+
+/// Device whose DMA address space a [`BufferPool`] maps its buffers into.
+#[derive(Debug, Copy, Clone)]
+pub enum DmaDevice<'d> {
+    Device(&'d Device),
+    VDevice(&'d VDevice),
+}
+
+impl<'d> From<&'d Device> for DmaDevice<'d> {
+    fn from(device: &'d Device) -> Self {
+        Self::Device(device)
+    }
+}
+
+impl<'d> From<&'d VDevice> for DmaDevice<'d> {
+    fn from(vdevice: &'d VDevice) -> Self {
+        Self::VDevice(vdevice)
+    }
+}
+
+impl DmaDevice<'_> {
+    fn map(&self, buffer: &FrameBuffer, direction: DmaBufferDirection) -> HailoResult<()> {
+        let (address, size) = (buffer.ptr.as_ptr().cast::<c_void>(), buffer.capacity());
+        let direction = c_int::from(direction);
+        check(unsafe {
+            match self {
+                Self::Device(device) => {
+                    hailo_device_dma_map_buffer(device.as_raw(), address, size, direction)
+                }
+                Self::VDevice(vdevice) => {
+                    hailo_vdevice_dma_map_buffer(vdevice.as_raw(), address, size, direction)
+                }
+            }
+        })
+    }
+
+    /// Unmaps and frees `buffer`.  If unmapping fails the memory is leaked
+    /// instead, since freeing memory the device may still write is unsound.
+    fn unmap(&self, buffer: FrameBuffer, direction: DmaBufferDirection) -> HailoResult<()> {
+        let (address, size) = (buffer.ptr.as_ptr().cast::<c_void>(), buffer.capacity());
+        let direction = c_int::from(direction);
+        check(unsafe {
+            match self {
+                Self::Device(device) => {
+                    hailo_device_dma_unmap_buffer(device.as_raw(), address, size, direction)
+                }
+                Self::VDevice(vdevice) => {
+                    hailo_vdevice_dma_unmap_buffer(vdevice.as_raw(), address, size, direction)
+                }
+            }
+        })
+        .inspect_err(|_| std::mem::forget(buffer))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BufferPool
+// ---------------------------------------------------------------------------
+
+/// Builder for [`BufferPool`].
+#[derive(Debug)]
+pub struct BufferPoolBuilder<'d> {
+    frame_size: usize,
+    preallocate: usize,
+    dma: Option<(DmaDevice<'d>, DmaBufferDirection)>,
+}
+
+impl<'d> BufferPoolBuilder<'d> {
+    /// Allocates `count` buffers up front instead of on first use.
+    pub fn preallocate(mut self, count: usize) -> Self {
+        self.preallocate = count;
+        self
+    }
+
+    /// Maps every buffer into `device` for `direction` when it is
+    /// allocated, for as long as the buffer lives.
+    pub fn dma_map(
+        mut self,
+        device: impl Into<DmaDevice<'d>>,
+        direction: DmaBufferDirection,
+    ) -> Self {
+        self.dma = Some((device.into(), direction));
+        self
+    }
+
+    /// Fails with `InvalidArgument` for a zero or unrepresentable frame
+    /// size, whether or not anything is preallocated.
+    pub fn build(self) -> HailoResult<BufferPool<'d>> {
+        FrameBuffer::layout(self.frame_size)?;
+        let pool = BufferPool {
+            frame_size: self.frame_size,
+            dma: self.dma,
+            free: Arc::new(Mutex::new(Some(Vec::with_capacity(self.preallocate)))),
+            allocated: AtomicUsize::new(0),
+        };
+        for _ in 0..self.preallocate {
+            let buffer = pool.allocate()?;
+            pool.give_back(buffer);
+        }
+        Ok(pool)
+    }
+}
+
+/// Recycling pool of equally sized [`FrameBuffer`]s.
+///
+/// Buffers are allocated on demand and never freed while the pool lives:
+/// dropping a [`PooledBuffer`] puts it back.  Dropping the pool unmaps and
+/// frees the buffers it holds; [`close`](Self::close) does the same and
+/// reports a failed unmap.  A buffer whose unmap fails is leaked, not freed.
+///
+/// Buffers still handed out when the pool is dropped are unaffected, so an
+/// in-flight transfer never loses its memory or mapping.  Each borrows the
+/// pool's device and unmaps and frees itself when dropped.
+pub struct BufferPool<'d> {
+    frame_size: usize,
+    dma: Option<(DmaDevice<'d>, DmaBufferDirection)>,
+    /// Buffers waiting to be reused; `None` once the pool is dropped.
+    free: Arc<Mutex<Option<Vec<FrameBuffer>>>>,
+    allocated: AtomicUsize,
+}
+
+impl<'d> BufferPool<'d> {
+    pub fn builder(frame_size: usize) -> BufferPoolBuilder<'d> {
+        BufferPoolBuilder {
+            frame_size,
+            preallocate: 0,
+            dma: None,
+        }
+    }
+
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    /// Buffers currently waiting in the pool.
+    pub fn available(&self) -> usize {
+        self.free.lock().unwrap().as_ref().map_or(0, Vec::len)
+    }
+
+    /// Buffers allocated so far, in the pool or handed out.
+    pub fn allocated(&self) -> usize {
+        self.allocated.load(Ordering::Relaxed)
+    }
+
+    /// Takes a free buffer, allocating (and mapping) a new one if none is
+    /// left.  Recycled buffers keep the previous frame's contents.
+    pub fn get(&self) -> HailoResult<PooledBuffer<'d>> {
+        let recycled = self.free.lock().unwrap().as_mut().and_then(Vec::pop);
+        let buffer = match recycled {
+            Some(buffer) => buffer,
+            None => self.allocate()?,
+        };
+        Ok(PooledBuffer {
+            buffer: Some(buffer),
+            home: Arc::clone(&self.free),
+            dma: self.dma,
+        })
+    }
+
+    fn allocate(&self) -> HailoResult<FrameBuffer> {
+        let buffer = FrameBuffer::new(self.frame_size)?;
+        if let Some((device, direction)) = &self.dma {
+            device.map(&buffer, *direction)?;
+        }
+        self.allocated.fetch_add(1, Ordering::Relaxed);
+        Ok(buffer)
+    }
+
+    fn give_back(&self, buffer: FrameBuffer) {
+        if let Some(free) = self.free.lock().unwrap().as_mut() {
+            free.push(buffer);
+        }
+    }
+
+    /// Unmaps and frees the buffers in the pool, like dropping it, but
+    /// returns the first unmap failure.  Every buffer is attempted; those
+    /// that failed to unmap are leaked.
+    pub fn close(self) -> HailoResult<()> {
+        self.release_free()
+    }
+
+    /// Closes the free list and releases its buffers.  Closing under the
+    /// lock means a buffer returned concurrently is released either here or
+    /// by its own drop.
+    fn release_free(&self) -> HailoResult<()> {
+        let free = self.free.lock().unwrap().take().unwrap_or_default();
+        let Some((device, direction)) = self.dma else {
+            return Ok(());
+        };
+        free.into_iter()
+            .map(|buffer| device.unmap(buffer, direction))
+            .fold(Ok(()), Result::and)
+    }
+}
+
+impl Drop for BufferPool<'_> {
+    fn drop(&mut self) {
+        // Use `close` to observe the result.
+        let _ = self.release_free();
+    }
+}
+
+impl fmt::Debug for BufferPool<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("frame_size", &self.frame_size)
+            .field("dma", &self.dma)
+            .field("available", &self.available())
+            .field("allocated", &self.allocated())
+            .finish()
+    }
+}
+
+/// Buffer borrowed from a [`BufferPool`]; returned to it on drop.
+///
+/// May outlive the pool but not the device it is mapped into.  Dropped after
+/// the pool, it unmaps and frees itself, or is leaked if the unmap fails.
+pub struct PooledBuffer<'d> {
+    buffer: Option<FrameBuffer>,
+    home: Arc<Mutex<Option<Vec<FrameBuffer>>>>,
+    dma: Option<(DmaDevice<'d>, DmaBufferDirection)>,
+}
+
+impl PooledBuffer<'_> {
+    fn buffer(&self) -> &FrameBuffer {
+        self.buffer.as_ref().expect("present until drop")
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer().capacity()
+    }
+}
+
+impl Deref for PooledBuffer<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.buffer()
+    }
+}
+
+impl DerefMut for PooledBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.buffer.as_mut().expect("present until drop")
+    }
+}
+
+impl AsRef<[u8]> for PooledBuffer<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsMut<[u8]> for PooledBuffer<'_> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+// SAFETY: derefs to the inner `FrameBuffer`, which is itself stable.
+unsafe impl StableBuffer for PooledBuffer<'static> {}
+
+impl Drop for PooledBuffer<'_> {
+    fn drop(&mut self) {
+        let Some(buffer) = self.buffer.take() else {
+            return;
+        };
+        let mut home = self.home.lock().unwrap();
+        match (home.as_mut(), self.dma) {
+            (Some(free), _) => free.push(buffer),
+            // The pool is gone; the device is still borrowed, so unmap here.
+            // A failure leaves the buffer leaked, as in `BufferPool::close`.
+            (None, Some((device, direction))) => {
+                drop(home);
+                let _ = device.unmap(buffer, direction);
+            }
+            (None, None) => drop(buffer),
+        }
+    }
+}
+
+impl fmt::Debug for PooledBuffer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PooledBuffer").field(self.buffer()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_buffers_are_page_aligned_and_zeroed() {
+        let page = page_size();
+        let mut buffer = FrameBuffer::new(page + 1).unwrap();
+        assert_eq!(buffer.as_ptr() as usize % page, 0);
+        assert_eq!(buffer.len(), page + 1);
+        assert_eq!(buffer.capacity(), 2 * page);
+        assert!(buffer.iter().all(|&b| b == 0));
+        buffer[page] = 7;
+        assert_eq!(buffer.as_ref()[page], 7);
+
+        assert_eq!(
+            FrameBuffer::new(0).unwrap_err(),
+            HailoError::InvalidArgument
+        );
+        assert_eq!(
+            FrameBuffer::new(usize::MAX).unwrap_err(),
+            HailoError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn frame_size_comes_from_hw_frame_size() {
+        // SAFETY: all-zero is valid for this plain-data struct.
+        let mut raw: hailo_stream_info_t = unsafe { std::mem::zeroed() };
+        raw.hw_frame_size = 640 * 640 * 3;
+        assert_eq!(FrameBuffer::for_frame(&raw).unwrap().len(), 640 * 640 * 3);
+        let info = StreamInfo::from(&raw);
+        assert_eq!(info.frame_size(), 640 * 640 * 3);
+    }
+
+    #[test]
+    fn pool_recycles_buffers() {
+        let pool = BufferPool::builder(1000).preallocate(2).build().unwrap();
+        assert_eq!((pool.available(), pool.allocated()), (2, 2));
+
+        let a = pool.get().unwrap();
+        let address = a.as_ptr();
+        let b = pool.get().unwrap();
+        let c = pool.get().unwrap();
+        assert_eq!((pool.available(), pool.allocated()), (0, 3));
+        assert_eq!(c.len(), 1000);
+
+        drop(a);
+        assert_eq!(pool.available(), 1);
+        assert_eq!(pool.get().unwrap().as_ptr(), address);
+
+        drop(pool);
+        drop((b, c));
+    }
+
+    #[test]
+    fn closing_pool_releases_free_buffers() {
+        fn stable<B: StableBuffer>() {}
+        stable::<PooledBuffer<'static>>();
+
+        let pool = BufferPool::builder(1000).preallocate(2).build().unwrap();
+        let outstanding = pool.get().unwrap();
+        assert_eq!(pool.close(), Ok(()));
+        assert_eq!(outstanding.len(), 1000);
+        drop(outstanding);
+    }
+
+    #[test]
+    fn pool_rejects_empty_frames() {
+        assert_eq!(
+            BufferPool::builder(0).build().unwrap_err(),
+            HailoError::InvalidArgument
+        );
+        assert_eq!(
+            BufferPool::builder(usize::MAX).build().unwrap_err(),
+            HailoError::InvalidArgument
+        );
+        let pool = BufferPool::builder(1000).build().unwrap();
+        assert_eq!((pool.available(), pool.allocated()), (0, 0));
+    }
+}
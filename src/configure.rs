@@ -0,0 +1,306 @@
+//! Safe construction of `hailo_configure_params_t`.
+//!
+//! The C struct is a nested fixed-array table addressed by name.
+//! [`ConfigureParamsBuilder`] records overrides keyed by network-group,
+//! stream and network name and applies them on top of the defaults from
+//! `hailo_init_configure_params_by_device` / `_by_vdevice`.
+
+use crate::constants::HAILO_MAX_NETWORKS_IN_NETWORK_GROUP;
+use crate::cstr::CFixedStr;
+use crate::device::Device;
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{hailo_init_configure_params_by_device, hailo_init_configure_params_by_vdevice};
+use crate::hef::Hef;
+use crate::typed_enums::{LatencyMeasurementFlags, PowerMode, StreamFlags};
+use crate::types::{hailo_configure_network_group_params_t, hailo_configure_params_t};
+use crate::vdevice::VDevice;
+
+/// Filled-in `hailo_configure_params_t`, ready for `hailo_configure_device`
+/// or `hailo_configure_vdevice`.
+pub struct ConfigureParams {
+    raw: Box<hailo_configure_params_t>,
+}
+
+impl ConfigureParams {
+    pub fn builder() -> ConfigureParamsBuilder {
+        ConfigureParamsBuilder::default()
+    }
+
+    fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers, bools, char arrays and unions
+        // of the same, for which all-zero is a valid value.
+        Self {
+            raw: unsafe { Box::new_zeroed().assume_init() },
+        }
+    }
+
+    pub fn as_raw(&self) -> &hailo_configure_params_t {
+        &self.raw
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut hailo_configure_params_t {
+        &mut *self.raw
+    }
+
+    /// Names of the network groups present in the params.
+    pub fn network_group_names(&self) -> Vec<String> {
+        self.groups()
+            .iter()
+            .map(|g| g.name.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn groups(&self) -> &[hailo_configure_network_group_params_t] {
+        let count = self
+            .raw
+            .network_group_params_count
+            .min(self.raw.network_group_params.len());
+        &self.raw.network_group_params[..count]
+    }
+
+    fn group_mut(
+        &mut self,
+        name: &str,
+    ) -> HailoResult<&mut hailo_configure_network_group_params_t> {
+        let count = self
+            .raw
+            .network_group_params_count
+            .min(self.raw.network_group_params.len());
+        self.raw.network_group_params[..count]
+            .iter_mut()
+            .find(|g| g.name.eq_str(name))
+            .ok_or(HailoError::NotFound)
+    }
+}
+
+impl std::fmt::Debug for ConfigureParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigureParams")
+            .field("network_groups", &self.network_group_names())
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct GroupOverrides {
+    name: String,
+    batch_size: Option<u16>,
+    power_mode: Option<PowerMode>,
+    latency: Option<LatencyMeasurementFlags>,
+    enable_kv_cache: Option<bool>,
+    stream_flags: Vec<(String, StreamFlags)>,
+    network_batch_sizes: Vec<(String, u16)>,
+}
+
+/// Records per-name overrides for [`ConfigureParams`].
+///
+/// Names are resolved when the params are built: an unknown network group,
+/// stream or network fails with `NotFound`.  The defaults already list every
+/// network in the HEF, so an unlisted name can only be a typo.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigureParamsBuilder {
+    groups: Vec<GroupOverrides>,
+}
+
+impl ConfigureParamsBuilder {
+    fn group(&mut self, name: &str) -> &mut GroupOverrides {
+        let index = match self.groups.iter().position(|g| g.name == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push(GroupOverrides {
+                    name: name.to_owned(),
+                    ..Default::default()
+                });
+                self.groups.len() - 1
+            }
+        };
+        &mut self.groups[index]
+    }
+
+    pub fn batch_size(mut self, group: &str, batch_size: u16) -> Self {
+        self.group(group).batch_size = Some(batch_size);
+        self
+    }
+
+    pub fn power_mode(mut self, group: &str, mode: PowerMode) -> Self {
+        self.group(group).power_mode = Some(mode);
+        self
+    }
+
+    pub fn latency(mut self, group: &str, flags: LatencyMeasurementFlags) -> Self {
+        self.group(group).latency = Some(flags);
+        self
+    }
+
+    pub fn enable_kv_cache(mut self, group: &str, enabled: bool) -> Self {
+        self.group(group).enable_kv_cache = Some(enabled);
+        self
+    }
+
+    /// Replaces the flags of stream `stream`, e.g. with [`StreamFlags::ASYNC`].
+    pub fn stream_flags(mut self, group: &str, stream: &str, flags: StreamFlags) -> Self {
+        self.group(group)
+            .stream_flags
+            .push((stream.to_owned(), flags));
+        self
+    }
+
+    pub fn network_batch_size(mut self, group: &str, network: &str, batch_size: u16) -> Self {
+        self.group(group)
+            .network_batch_sizes
+            .push((network.to_owned(), batch_size));
+        self
+    }
+
+    /// Starts from `hailo_init_configure_params_by_device`.
+    pub fn build_for_device(&self, device: &Device, hef: &Hef) -> HailoResult<ConfigureParams> {
+        let mut params = ConfigureParams::zeroed();
+        check(unsafe {
+            hailo_init_configure_params_by_device(
+                device.as_raw(),
+                hef.as_raw(),
+                params.as_mut_ptr(),
+            )
+        })?;
+        self.apply(&mut params)?;
+        Ok(params)
+    }
+
+    /// Starts from `hailo_init_configure_params_by_vdevice`.
+    pub fn build_for_vdevice(&self, vdevice: &VDevice, hef: &Hef) -> HailoResult<ConfigureParams> {
+        let mut params = ConfigureParams::zeroed();
+        check(unsafe {
+            hailo_init_configure_params_by_vdevice(
+                vdevice.as_raw(),
+                hef.as_raw(),
+                params.as_mut_ptr(),
+            )
+        })?;
+        self.apply(&mut params)?;
+        Ok(params)
+    }
+
+    fn apply(&self, params: &mut ConfigureParams) -> HailoResult<()> {
+        for overrides in &self.groups {
+            let group = params.group_mut(&overrides.name)?;
+            if let Some(batch_size) = overrides.batch_size {
+                group.batch_size = batch_size;
+            }
+            if let Some(mode) = overrides.power_mode {
+                group.power_mode = mode.into();
+            }
+            if let Some(flags) = overrides.latency {
+                group.latency = flags.into();
+            }
+            if let Some(enabled) = overrides.enable_kv_cache {
+                group.enable_kv_cache = enabled;
+            }
+
+            for (stream, flags) in &overrides.stream_flags {
+                let count = group
+                    .stream_params_by_name_count
+                    .min(group.stream_params_by_name.len());
+                let entry = group.stream_params_by_name[..count]
+                    .iter_mut()
+                    .find(|s| s.name.eq_str(stream))
+                    .ok_or(HailoError::NotFound)?;
+                entry.stream_params.flags = (*flags).into();
+            }
+
+            for (network, batch_size) in &overrides.network_batch_sizes {
+                let count = group
+                    .network_params_by_name_count
+                    .min(HAILO_MAX_NETWORKS_IN_NETWORK_GROUP);
+                let entry = group.network_params_by_name[..count]
+                    .iter_mut()
+                    .find(|n| n.name.eq_str(network))
+                    .ok_or(HailoError::NotFound)?;
+                entry.network_params.batch_size = *batch_size;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{
+        HAILO_LATENCY_MEASURE, HAILO_POWER_MODE_ULTRA_PERFORMANCE, HAILO_STREAM_FLAGS_ASYNC,
+    };
+
+    /// One group `yolov8` with stream `yolov8/input_layer1` and network
+    /// `yolov8/yolov8`, as `hailo_init_configure_params_*` would produce.
+    fn sample() -> ConfigureParams {
+        let mut params = ConfigureParams::zeroed();
+        params.raw.network_group_params_count = 1;
+        let group = &mut params.raw.network_group_params[0];
+        group.name.set("yolov8").unwrap();
+        group.stream_params_by_name_count = 1;
+        group.stream_params_by_name[0]
+            .name
+            .set("yolov8/input_layer1")
+            .unwrap();
+        group.network_params_by_name_count = 1;
+        group.network_params_by_name[0]
+            .name
+            .set("yolov8/yolov8")
+            .unwrap();
+        params
+    }
+
+    #[test]
+    fn overrides_are_applied_by_name() {
+        let mut params = sample();
+        ConfigureParams::builder()
+            .batch_size("yolov8", 4)
+            .power_mode("yolov8", PowerMode::UltraPerformance)
+            .latency("yolov8", LatencyMeasurementFlags::MEASURE)
+            .enable_kv_cache("yolov8", true)
+            .stream_flags("yolov8", "yolov8/input_layer1", StreamFlags::ASYNC)
+            .network_batch_size("yolov8", "yolov8/yolov8", 2)
+            .apply(&mut params)
+            .unwrap();
+
+        let group = &params.raw.network_group_params[0];
+        assert_eq!(group.batch_size, 4);
+        assert_eq!(group.power_mode, HAILO_POWER_MODE_ULTRA_PERFORMANCE);
+        assert_eq!(group.latency, HAILO_LATENCY_MEASURE);
+        assert!(group.enable_kv_cache);
+        assert_eq!(
+            group.stream_params_by_name[0].stream_params.flags,
+            HAILO_STREAM_FLAGS_ASYNC
+        );
+        assert_eq!(group.network_params_by_name_count, 1);
+        assert_eq!(group.network_params_by_name[0].network_params.batch_size, 2);
+        assert_eq!(params.network_group_names(), ["yolov8"]);
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        let unknown_group = ConfigureParams::builder().batch_size("resnet", 1);
+        assert_eq!(
+            unknown_group.apply(&mut sample()),
+            Err(HailoError::NotFound)
+        );
+
+        let unknown_stream =
+            ConfigureParams::builder().stream_flags("yolov8", "nope", StreamFlags::ASYNC);
+        assert_eq!(
+            unknown_stream.apply(&mut sample()),
+            Err(HailoError::NotFound)
+        );
+
+        let unknown_network =
+            ConfigureParams::builder().network_batch_size("yolov8", "yolov8/typo", 2);
+        let mut params = sample();
+        assert_eq!(
+            unknown_network.apply(&mut params),
+            Err(HailoError::NotFound)
+        );
+        assert_eq!(
+            params.raw.network_group_params[0].network_params_by_name_count,
+            1
+        );
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! These mirror the `#define` constants in `<hailo/hailort.h>`.
 
+use std::ffi::CStr;
+
 // --- Name / ID lengths ------------------------------------------------------
 
 pub const HAILO_MAX_NAME_SIZE: usize = 128;
@@ -51,6 +53,8 @@ pub const HAILO_DEFAULT_VSTREAM_TIMEOUT_MS: u32 = 10_000;
 pub const HAILO_DEFAULT_ASYNC_INFER_TIMEOUT_MS: u32 = 10_000;
 pub const HAILO_DEFAULT_ASYNC_INFER_QUEUE_SIZE: u32 = 2;
 pub const HAILO_DEFAULT_DEVICE_COUNT: u32 = 1;
+/// Group that gives each vdevice its own devices.
+pub const HAILO_DEFAULT_VDEVICE_GROUP_ID: &CStr = c"UNIQUE";
 pub const HAILO_DEFAULT_ETH_SCAN_TIMEOUT_MS: u32 = 10_000;
 pub const HAILO_DEFAULT_ETH_DEVICE_PORT: u16 = 0;
 pub const HAILO_DEFAULT_ETH_MAX_PAYLOAD_SIZE: u32 = 1456;
@@ -121,6 +125,7 @@ mod tests {
         assert_eq!(HAILO_DEFAULT_ASYNC_INFER_TIMEOUT_MS, 10_000);
         assert_eq!(HAILO_DEFAULT_ASYNC_INFER_QUEUE_SIZE, 2);
         assert_eq!(HAILO_DEFAULT_DEVICE_COUNT, 1);
+        assert_eq!(HAILO_DEFAULT_VDEVICE_GROUP_ID, c"UNIQUE");
         assert_eq!(HAILO_DEFAULT_ETH_SCAN_TIMEOUT_MS, 10_000);
         assert_eq!(HAILO_DEFAULT_ETH_DEVICE_PORT, 0);
         assert_eq!(HAILO_DEFAULT_ETH_MAX_PAYLOAD_SIZE, 1456);
@@ -132,7 +137,7 @@ mod tests {
         assert_eq!(HAILO_SCHEDULER_PRIORITY_MIN, 0);
         assert_eq!(HAILO_SCHEDULER_PRIORITY_NORMAL, 16);
         assert_eq!(HAILO_SCHEDULER_PRIORITY_MAX, 31);
-        assert!(HAILO_SCHEDULER_PRIORITY_MIN <= HAILO_SCHEDULER_PRIORITY_NORMAL);
-        assert!(HAILO_SCHEDULER_PRIORITY_NORMAL <= HAILO_SCHEDULER_PRIORITY_MAX);
+        const { assert!(HAILO_SCHEDULER_PRIORITY_MIN < HAILO_SCHEDULER_PRIORITY_NORMAL) };
+        const { assert!(HAILO_SCHEDULER_PRIORITY_NORMAL < HAILO_SCHEDULER_PRIORITY_MAX) };
     }
 }
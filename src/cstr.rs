@@ -0,0 +1,164 @@
+//! Fixed-size C string fields (`[c_char; N]`).
+//!
+//! Names in [`types`](crate::types) are stored inline, NUL-terminated when
+//! shorter than their array.  [`CFixedStr`] reads and writes them without
+//! `unsafe` or manual byte loops, and every struct whose identity is its
+//! name implements `Display` and `PartialEq<&str>` on that name:
+//!
+//! ```
+//! use hailort_sys::{CFixedStr, hailo_layer_name_t};
+//!
+//! let mut layer = hailo_layer_name_t { name: [0; 128] };
+//! layer.name.set("yolov8/conv1").unwrap();
+//! assert_eq!(layer.name.as_str(), Ok("yolov8/conv1"));
+//! assert!(layer == "yolov8/conv1");
+//! assert_eq!(layer.to_string(), "yolov8/conv1");
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::os::raw::c_char;
+use std::str::Utf8Error;
+
+use crate::error::{HailoError, HailoResult};
+use crate::types::*;
+
+/// Access to a fixed-size, optionally NUL-terminated C string field.
+///
+/// Implemented for `[c_char]`, so it applies to every `[c_char; N]` array.
+pub trait CFixedStr {
+    /// Bytes up to the first NUL, or the whole field if there is none.
+    fn to_bytes(&self) -> &[u8];
+
+    /// The contents up to the first NUL, if they are valid UTF-8.
+    fn as_str(&self) -> Result<&str, Utf8Error> {
+        std::str::from_utf8(self.to_bytes())
+    }
+
+    /// The contents up to the first NUL, with invalid UTF-8 replaced.
+    fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self.to_bytes())
+    }
+
+    /// Whether the field holds exactly `s`.
+    fn eq_str(&self, s: &str) -> bool {
+        self.to_bytes() == s.as_bytes()
+    }
+
+    /// Replaces the contents with `s`, NUL-padding the rest of the field.
+    ///
+    /// Fails with `InvalidArgument` if `s` contains NUL or does not fit
+    /// together with its terminator.
+    fn set(&mut self, s: &str) -> HailoResult<()>;
+}
+
+impl CFixedStr for [c_char] {
+    fn to_bytes(&self) -> &[u8] {
+        // SAFETY: `c_char` is `i8` or `u8`, both with the layout of `u8`.
+        let bytes = unsafe { std::slice::from_raw_parts(self.as_ptr().cast::<u8>(), self.len()) };
+        let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        &bytes[..len]
+    }
+
+    fn set(&mut self, s: &str) -> HailoResult<()> {
+        if s.len() >= self.len() || s.as_bytes().contains(&0) {
+            return Err(HailoError::InvalidArgument);
+        }
+        self.fill(0);
+        for (dst, src) in self.iter_mut().zip(s.bytes()) {
+            *dst = src as c_char;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! named_structs {
+    ($($ty:ty => $field:ident,)*) => {
+        $(
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(&self.$field.to_string_lossy())
+                }
+            }
+
+            impl PartialEq<str> for $ty {
+                fn eq(&self, other: &str) -> bool {
+                    self.$field.eq_str(other)
+                }
+            }
+
+            impl PartialEq<&str> for $ty {
+                fn eq(&self, other: &&str) -> bool {
+                    self.$field.eq_str(other)
+                }
+            }
+        )*
+    };
+}
+
+named_structs! {
+    hailo_device_id_t => id,
+    hailo_stream_parameters_by_name_t => name,
+    hailo_input_vstream_params_by_name_t => name,
+    hailo_output_vstream_params_by_name_t => name,
+    hailo_output_vstream_name_by_group_t => name,
+    hailo_stream_info_t => name,
+    hailo_vstream_info_t => name,
+    hailo_network_parameters_by_name_t => name,
+    hailo_configure_network_group_params_t => name,
+    hailo_network_group_info_t => name,
+    hailo_layer_name_t => name,
+    hailo_network_info_t => name,
+    hailo_stream_raw_buffer_by_name_t => name,
+    hailo_rate_limit_t => stream_name,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::HAILO_MAX_DEVICE_ID_LENGTH;
+
+    #[test]
+    fn reads_stop_at_nul() {
+        let mut raw = [0 as c_char; 8];
+        for (dst, src) in raw.iter_mut().zip(b"abc") {
+            *dst = *src as c_char;
+        }
+        assert_eq!(raw.as_str(), Ok("abc"));
+        assert_eq!(raw.to_string_lossy(), "abc");
+
+        let full = [b'x' as c_char; 4];
+        assert_eq!(full.as_str(), Ok("xxxx"));
+    }
+
+    #[test]
+    fn invalid_utf8_is_checked_or_replaced() {
+        let raw = [b'a' as c_char, 0xff_u8 as c_char, 0];
+        assert!(raw.as_str().is_err());
+        assert_eq!(raw.to_string_lossy(), "a\u{fffd}");
+    }
+
+    #[test]
+    fn set_respects_exact_capacity() {
+        let mut raw = [b'z' as c_char; 6];
+        raw.set("abc").unwrap();
+        assert!(raw.eq_str("abc"));
+        assert!(!raw.eq_str("ab"));
+        assert!(!raw.eq_str("abcd"));
+        assert_eq!(raw[3..], [0; 3]);
+        assert_eq!(raw.set("abcdef"), Err(HailoError::InvalidArgument));
+        assert_eq!(raw.set("a\0b"), Err(HailoError::InvalidArgument));
+        assert!(raw.set("abcde").is_ok());
+    }
+
+    #[test]
+    fn named_structs_display_and_compare() {
+        let mut id = hailo_device_id_t {
+            id: [0; HAILO_MAX_DEVICE_ID_LENGTH],
+        };
+        id.id.set("0000:01:00.0").unwrap();
+        assert_eq!(id.to_string(), "0000:01:00.0");
+        assert!(id == "0000:01:00.0");
+        assert!(id != "0000:02:00.0");
+    }
+}
@@ -0,0 +1,473 @@
+//! Safe, owning wrapper around a physical `hailo_device`.
+//!
+//! [`Device`] releases its handle on drop.  Discovery and identification
+//! return owned Rust structs, so no C buffers outlive the call that filled
+//! them.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::{ManuallyDrop, MaybeUninit};
+use std::ops::Deref;
+use std::os::raw::c_char;
+use std::ptr;
+
+use crate::constants::HAILO_MAX_DEVICE_ID_LENGTH;
+use crate::cstr::CFixedStr;
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{
+    hailo_core_identify, hailo_create_device_by_id, hailo_create_pcie_device, hailo_get_device_id,
+    hailo_get_driver_version, hailo_get_extended_device_information, hailo_identify,
+    hailo_release_device, hailo_scan_devices, hailo_scan_pcie_devices,
+};
+use crate::handles::hailo_device;
+use crate::typed_enums::{DeviceArchitecture, DeviceBootSource};
+use crate::types::{
+    hailo_device_id_t, hailo_device_supported_features_t, hailo_firmware_version_t,
+    hailo_pcie_device_info_t, hailo_version_t,
+};
+use crate::util::fetch_array;
+
+/// Initial capacity for the scan calls; grown on `HAILO_INSUFFICIENT_BUFFER`.
+const SCAN_CAPACITY: usize = 8;
+
+// ---------------------------------------------------------------------------
+// Owned info structs
+// ---------------------------------------------------------------------------
+
+/// Library, driver or firmware version triple.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub revision: u32,
+}
+
+impl From<hailo_version_t> for Version {
+    fn from(v: hailo_version_t) -> Self {
+        Self {
+            major: v.major,
+            minor: v.minor,
+            revision: v.revision,
+        }
+    }
+}
+
+impl From<hailo_firmware_version_t> for Version {
+    fn from(v: hailo_firmware_version_t) -> Self {
+        Self {
+            major: v.major,
+            minor: v.minor,
+            revision: v.revision,
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.revision)
+    }
+}
+
+/// Device identifier as reported by `hailo_scan_devices`, e.g. `0000:01:00.0`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeviceId(String);
+
+impl DeviceId {
+    /// Fails with `InvalidArgument` if `id` does not fit
+    /// `HAILO_MAX_DEVICE_ID_LENGTH` including its terminator, or contains NUL.
+    pub fn new(id: impl Into<String>) -> HailoResult<Self> {
+        let id = id.into();
+        if id.len() >= HAILO_MAX_DEVICE_ID_LENGTH || id.as_bytes().contains(&0) {
+            return Err(HailoError::InvalidArgument);
+        }
+        Ok(Self(id))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Builds the null-terminated C representation.
+    ///
+    /// Fails with `InvalidArgument` for an id read back from a full-width,
+    /// unterminated field.
+    pub fn to_raw(&self) -> HailoResult<hailo_device_id_t> {
+        let mut raw = hailo_device_id_t {
+            id: [0; HAILO_MAX_DEVICE_ID_LENGTH],
+        };
+        raw.id.set(&self.0)?;
+        Ok(raw)
+    }
+}
+
+impl From<&hailo_device_id_t> for DeviceId {
+    fn from(raw: &hailo_device_id_t) -> Self {
+        Self(raw.id.to_string_lossy().into_owned())
+    }
+}
+
+impl fmt::Display for DeviceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// PCIe address of a Hailo device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PcieDeviceInfo {
+    pub domain: u32,
+    pub bus: u32,
+    pub device: u32,
+    pub func: u32,
+}
+
+impl From<hailo_pcie_device_info_t> for PcieDeviceInfo {
+    fn from(raw: hailo_pcie_device_info_t) -> Self {
+        Self {
+            domain: raw.domain,
+            bus: raw.bus,
+            device: raw.device,
+            func: raw.func,
+        }
+    }
+}
+
+impl From<PcieDeviceInfo> for hailo_pcie_device_info_t {
+    fn from(info: PcieDeviceInfo) -> Self {
+        Self {
+            domain: info.domain,
+            bus: info.bus,
+            device: info.device,
+            func: info.func,
+        }
+    }
+}
+
+impl fmt::Display for PcieDeviceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04x}:{:02x}:{:02x}.{}",
+            self.domain, self.bus, self.device, self.func
+        )
+    }
+}
+
+/// Result of [`Device::identify`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceIdentity {
+    pub protocol_version: u32,
+    pub fw_version: Version,
+    pub logger_version: u32,
+    pub board_name: String,
+    pub is_release: bool,
+    pub extended_context_switch_buffer: bool,
+    pub extended_fw_check: bool,
+    pub device_architecture: DeviceArchitecture,
+    pub serial_number: String,
+    pub part_number: String,
+    pub product_name: String,
+}
+
+/// Result of [`Device::core_identify`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CoreInformation {
+    pub is_release: bool,
+    pub extended_context_switch_buffer: bool,
+    pub extended_fw_check: bool,
+    pub fw_version: Version,
+}
+
+/// Result of [`Device::extended_info`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendedDeviceInfo {
+    pub neural_network_core_clock_rate: u32,
+    pub supported_features: SupportedFeatures,
+    pub boot_source: DeviceBootSource,
+    pub soc_id: Vec<u8>,
+    pub lcs: u8,
+    pub eth_mac_address: [u8; 6],
+    pub unit_level_tracking_id: Vec<u8>,
+    pub soc_pm_values: Vec<u8>,
+    pub gpio_mask: u16,
+}
+
+/// Feature bits from `hailo_device_supported_features_t`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SupportedFeatures {
+    pub ethernet: bool,
+    pub mipi: bool,
+    pub pcie: bool,
+    pub current_monitoring: bool,
+    pub mdio: bool,
+    pub power_measurement: bool,
+}
+
+impl From<hailo_device_supported_features_t> for SupportedFeatures {
+    fn from(raw: hailo_device_supported_features_t) -> Self {
+        Self {
+            ethernet: raw.ethernet,
+            mipi: raw.mipi,
+            pcie: raw.pcie,
+            current_monitoring: raw.current_monitoring,
+            mdio: raw.mdio,
+            power_measurement: raw.power_measurement,
+        }
+    }
+}
+
+/// Decodes a length-prefixed name field, clamping a bogus length to the array.
+fn string_with_length(chars: &[c_char], len: u8) -> String {
+    chars[..(len as usize).min(chars.len())]
+        .to_string_lossy()
+        .into_owned()
+}
+
+// ---------------------------------------------------------------------------
+// Device
+// ---------------------------------------------------------------------------
+
+/// Owned handle to a physical Hailo device.  Released on drop.
+///
+/// `Send` and `Sync`: HailoRT serialises control requests per device, so one
+/// thread can poll [`health`](Self::health) while another runs inference.
+#[derive(Debug)]
+pub struct Device {
+    raw: hailo_device,
+}
+
+// SAFETY: every `hailo_device` call takes the device's internal control lock.
+unsafe impl Send for Device {}
+// SAFETY: as above; no method hands out interior state without that lock.
+unsafe impl Sync for Device {}
+
+impl Device {
+    /// Lists every device visible to the runtime (`hailo_scan_devices`).
+    pub fn scan() -> HailoResult<Vec<DeviceId>> {
+        let ids = fetch_array::<hailo_device_id_t>(SCAN_CAPACITY, |ptr, len| unsafe {
+            hailo_scan_devices(ptr::null_mut(), ptr, len)
+        })?;
+        Ok(ids.iter().map(DeviceId::from).collect())
+    }
+
+    /// Lists PCIe devices (`hailo_scan_pcie_devices`).
+    pub fn scan_pcie() -> HailoResult<Vec<PcieDeviceInfo>> {
+        let infos = fetch_array::<hailo_pcie_device_info_t>(SCAN_CAPACITY, |ptr, len| unsafe {
+            let capacity = *len;
+            hailo_scan_pcie_devices(ptr, capacity, len)
+        })?;
+        Ok(infos.into_iter().map(PcieDeviceInfo::from).collect())
+    }
+
+    /// Opens the device with the given id (`hailo_create_device_by_id`).
+    pub fn open(id: &DeviceId) -> HailoResult<Self> {
+        let raw_id = id.to_raw()?;
+        let mut raw = ptr::null_mut();
+        check(unsafe { hailo_create_device_by_id(&raw_id, &mut raw) })?;
+        Ok(Self { raw })
+    }
+
+    /// Opens the device at the given PCIe address (`hailo_create_pcie_device`).
+    pub fn open_pcie(info: PcieDeviceInfo) -> HailoResult<Self> {
+        let mut raw_info = hailo_pcie_device_info_t::from(info);
+        let mut raw = ptr::null_mut();
+        check(unsafe { hailo_create_pcie_device(&mut raw_info, &mut raw) })?;
+        Ok(Self { raw })
+    }
+
+    /// Opens the only PCIe device present; fails if there is none or several.
+    pub fn open_any_pcie() -> HailoResult<Self> {
+        let mut raw = ptr::null_mut();
+        check(unsafe { hailo_create_pcie_device(ptr::null_mut(), &mut raw) })?;
+        Ok(Self { raw })
+    }
+
+    /// Takes ownership of a handle created elsewhere.
+    ///
+    /// # Safety
+    /// `raw` must be a valid device handle that nothing else releases.
+    pub unsafe fn from_raw(raw: hailo_device) -> Self {
+        Self { raw }
+    }
+
+    /// The underlying handle, still owned by `self`.
+    pub fn as_raw(&self) -> hailo_device {
+        self.raw
+    }
+
+    /// Gives up ownership; the caller must call `hailo_release_device`.
+    pub fn into_raw(self) -> hailo_device {
+        let raw = self.raw;
+        std::mem::forget(self);
+        raw
+    }
+
+    pub fn identify(&self) -> HailoResult<DeviceIdentity> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_identify(self.raw, raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(DeviceIdentity {
+            protocol_version: raw.protocol_version,
+            fw_version: raw.fw_version.into(),
+            logger_version: raw.logger_version,
+            board_name: string_with_length(&raw.board_name, raw.board_name_length),
+            is_release: raw.is_release,
+            extended_context_switch_buffer: raw.extended_context_switch_buffer,
+            extended_fw_check: raw.extended_fw_check,
+            device_architecture: raw.device_architecture.into(),
+            serial_number: string_with_length(&raw.serial_number, raw.serial_number_length),
+            part_number: string_with_length(&raw.part_number, raw.part_number_length),
+            product_name: string_with_length(&raw.product_name, raw.product_name_length),
+        })
+    }
+
+    pub fn core_identify(&self) -> HailoResult<CoreInformation> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_core_identify(self.raw, raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(CoreInformation {
+            is_release: raw.is_release,
+            extended_context_switch_buffer: raw.extended_context_switch_buffer,
+            extended_fw_check: raw.extended_fw_check,
+            fw_version: raw.fw_version.into(),
+        })
+    }
+
+    pub fn extended_info(&self) -> HailoResult<ExtendedDeviceInfo> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_get_extended_device_information(self.raw, raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(ExtendedDeviceInfo {
+            neural_network_core_clock_rate: raw.neural_network_core_clock_rate,
+            supported_features: raw.supported_features.into(),
+            boot_source: raw.boot_source.into(),
+            soc_id: raw.soc_id.to_vec(),
+            lcs: raw.lcs,
+            eth_mac_address: raw.eth_mac_address,
+            unit_level_tracking_id: raw.unit_level_tracking_id.to_vec(),
+            soc_pm_values: raw.soc_pm_values.to_vec(),
+            gpio_mask: raw.gpio_mask,
+        })
+    }
+
+    pub fn device_id(&self) -> HailoResult<DeviceId> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_get_device_id(self.raw, raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(DeviceId::from(&raw))
+    }
+
+    pub fn driver_version(&self) -> HailoResult<Version> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_get_driver_version(self.raw, raw.as_mut_ptr()) })?;
+        Ok(unsafe { raw.assume_init() }.into())
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        unsafe { hailo_release_device(self.raw) };
+    }
+}
+
+/// A device handle owned by something else, typically a
+/// [`VDevice`](crate::VDevice).  Derefs to [`Device`] but never releases it.
+#[derive(Debug)]
+pub struct DeviceRef<'a> {
+    device: ManuallyDrop<Device>,
+    _owner: PhantomData<&'a ()>,
+}
+
+impl DeviceRef<'_> {
+    /// # Safety
+    /// `raw` must stay valid for the chosen lifetime.
+    pub(crate) unsafe fn new(raw: hailo_device) -> Self {
+        Self {
+            device: ManuallyDrop::new(Device { raw }),
+            _owner: PhantomData,
+        }
+    }
+}
+
+impl Deref for DeviceRef<'_> {
+    type Target = Device;
+
+    fn deref(&self) -> &Device {
+        &self.device
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn device_id_round_trips_through_raw() {
+        let id = DeviceId::new("0000:01:00.0").unwrap();
+        let raw = id.to_raw().unwrap();
+        assert_eq!(raw.id[HAILO_MAX_DEVICE_ID_LENGTH - 1], 0);
+        assert_eq!(DeviceId::from(&raw), id);
+
+        let full = DeviceId::from(&hailo_device_id_t {
+            id: [b'x' as c_char; HAILO_MAX_DEVICE_ID_LENGTH],
+        });
+        assert_eq!(full.as_str().len(), HAILO_MAX_DEVICE_ID_LENGTH);
+        assert_eq!(full.to_raw().unwrap_err(), HailoError::InvalidArgument);
+    }
+
+    #[test]
+    fn device_id_rejects_overlong_and_nul() {
+        assert!(DeviceId::new("x".repeat(HAILO_MAX_DEVICE_ID_LENGTH - 1)).is_ok());
+        assert_eq!(
+            DeviceId::new("x".repeat(HAILO_MAX_DEVICE_ID_LENGTH)),
+            Err(HailoError::InvalidArgument)
+        );
+        assert_eq!(DeviceId::new("a\0b"), Err(HailoError::InvalidArgument));
+    }
+
+    #[test]
+    fn pcie_info_displays_as_bdf() {
+        let info = PcieDeviceInfo {
+            domain: 0,
+            bus: 1,
+            device: 0,
+            func: 0,
+        };
+        assert_eq!(info.to_string(), "0000:01:00.0");
+        assert_eq!(
+            PcieDeviceInfo::from(hailo_pcie_device_info_t::from(info)),
+            info
+        );
+    }
+
+    #[test]
+    fn length_prefixed_names_are_clamped() {
+        let raw = [b'a' as c_char; 4];
+        assert_eq!(string_with_length(&raw, 2), "aa");
+        assert_eq!(string_with_length(&raw, 200), "aaaa");
+    }
+
+    #[test]
+    fn version_displays_dotted() {
+        let v = Version::from(hailo_version_t {
+            major: 4,
+            minor: 20,
+            revision: 1,
+        });
+        assert_eq!(v.to_string(), "4.20.1");
+    }
+
+    #[test]
+    fn device_handles_are_send_and_sync() {
+        fn send_sync<T: Send + Sync>() {}
+        send_sync::<Device>();
+        send_sync::<DeviceRef<'_>>();
+        send_sync::<crate::VDevice>();
+        send_sync::<crate::Hef>();
+        send_sync::<crate::ConfiguredNetworkGroup<'_>>();
+        send_sync::<crate::ActivatedNetworkGroup<'_>>();
+        send_sync::<crate::NotificationSubscription<'_>>();
+        send_sync::<crate::Watchdog<'_>>();
+        send_sync::<crate::BufferPool<'_>>();
+    }
+}
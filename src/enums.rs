@@ -139,6 +139,19 @@ pub const HAILO_FORMAT_ORDER_HAILO_NMS_ON_CHIP: hailo_format_order_t = 21;
 pub const HAILO_FORMAT_ORDER_HAILO_NMS_BY_CLASS: hailo_format_order_t = 22;
 pub const HAILO_FORMAT_ORDER_HAILO_NMS_BY_SCORE: hailo_format_order_t = 23;
 
+/// True for the `HAILO_FORMAT_ORDER_HAILO_NMS*` orders, whose shape union
+/// holds the NMS branch rather than a 3D tensor shape.
+pub(crate) fn is_nms_order(order: hailo_format_order_t) -> bool {
+    matches!(
+        order,
+        HAILO_FORMAT_ORDER_HAILO_NMS
+            | HAILO_FORMAT_ORDER_HAILO_NMS_WITH_BYTE_MASK
+            | HAILO_FORMAT_ORDER_HAILO_NMS_ON_CHIP
+            | HAILO_FORMAT_ORDER_HAILO_NMS_BY_CLASS
+            | HAILO_FORMAT_ORDER_HAILO_NMS_BY_SCORE
+    )
+}
+
 pub type hailo_format_flags_t = c_int;
 pub const HAILO_FORMAT_FLAGS_NONE: hailo_format_flags_t = 0;
 pub const HAILO_FORMAT_FLAGS_QUANTIZED: hailo_format_flags_t = 1;
@@ -405,6 +418,13 @@ mod tests {
         assert_eq!(HAILO_FORMAT_FLAGS_TRANSPOSED, 2);
     }
 
+    #[test]
+    fn nms_orders() {
+        assert!(is_nms_order(HAILO_FORMAT_ORDER_HAILO_NMS));
+        assert!(is_nms_order(HAILO_FORMAT_ORDER_HAILO_NMS_BY_SCORE));
+        assert!(!is_nms_order(HAILO_FORMAT_ORDER_NHWC));
+    }
+
     #[test]
     fn stream_enum_values() {
         assert_eq!(HAILO_STREAM_NO_TRANSFORM, 0);
@@ -496,7 +516,10 @@ mod tests {
         assert_eq!(HAILO_NOTIFICATION_ID_CONTEXT_SWITCH_BREAKPOINT_REACHED, 9);
         assert_eq!(HAILO_NOTIFICATION_ID_HEALTH_MONITOR_CLOCK_CHANGED_EVENT, 10);
         assert_eq!(HAILO_NOTIFICATION_ID_HW_INFER_MANAGER_INFER_DONE, 11);
-        assert_eq!(HAILO_NOTIFICATION_ID_CONTEXT_SWITCH_RUN_TIME_ERROR_EVENT, 12);
+        assert_eq!(
+            HAILO_NOTIFICATION_ID_CONTEXT_SWITCH_RUN_TIME_ERROR_EVENT,
+            12
+        );
         assert_eq!(HAILO_NOTIFICATION_ID_NN_CORE_CRC_ERROR_EVENT, 13);
         assert_eq!(HAILO_NOTIFICATION_ID_THROTTLING_STATE_CHANGE_EVENT, 14);
         assert_eq!(HAILO_NOTIFICATION_ID_COUNT, 15);
@@ -0,0 +1,312 @@
+//! Typed error for non-success `hailo_status` codes.
+//!
+//! [`HailoError`] has one variant per named status in [`crate::status`], plus
+//! [`HailoError::Unknown`] so codes added by newer runtimes are never lost.
+//! Use [`check`] to turn a raw return code into a `Result`.
+
+use std::ffi::CStr;
+use std::fmt;
+
+use crate::ffi::hailo_get_status_message;
+use crate::status::*;
+
+/// `Result` alias used by the safe wrappers in this crate.
+pub type HailoResult<T> = Result<T, HailoError>;
+
+macro_rules! hailo_errors {
+    ($($status:ident => $variant:ident,)*) => {
+        /// A non-success `hailo_status`.
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum HailoError {
+            $(
+                #[doc = concat!("`", stringify!($status), "`")]
+                $variant,
+            )*
+            /// A status code this crate has no name for.
+            Unknown(hailo_status),
+        }
+
+        impl HailoError {
+            /// Returns `None` for `HAILO_SUCCESS`.
+            pub fn from_status(status: hailo_status) -> Option<Self> {
+                match status {
+                    HAILO_SUCCESS => None,
+                    $($status => Some(Self::$variant),)*
+                    other => Some(Self::Unknown(other)),
+                }
+            }
+
+            /// The raw C status code.
+            pub fn status(self) -> hailo_status {
+                match self {
+                    $(Self::$variant => $status,)*
+                    Self::Unknown(status) => status,
+                }
+            }
+
+            /// The C constant name, e.g. `"HAILO_TIMEOUT"`.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => stringify!($status),)*
+                    Self::Unknown(_) => "HAILO_UNKNOWN_STATUS",
+                }
+            }
+        }
+    };
+}
+
+hailo_errors! {
+    HAILO_UNINITIALIZED => Uninitialized,
+    HAILO_INVALID_ARGUMENT => InvalidArgument,
+    HAILO_OUT_OF_HOST_MEMORY => OutOfHostMemory,
+    HAILO_TIMEOUT => Timeout,
+    HAILO_INSUFFICIENT_BUFFER => InsufficientBuffer,
+    HAILO_INVALID_OPERATION => InvalidOperation,
+    HAILO_NOT_IMPLEMENTED => NotImplemented,
+    HAILO_INTERNAL_FAILURE => InternalFailure,
+    HAILO_DATA_ALIGNMENT_FAILURE => DataAlignmentFailure,
+    HAILO_CHUNK_TOO_LARGE => ChunkTooLarge,
+    HAILO_INVALID_LOGGER_LEVEL => InvalidLoggerLevel,
+    HAILO_CLOSE_FAILURE => CloseFailure,
+    HAILO_OPEN_FILE_FAILURE => OpenFileFailure,
+    HAILO_FILE_OPERATION_FAILURE => FileOperationFailure,
+    HAILO_UNSUPPORTED_CONTROL_PROTOCOL_VERSION => UnsupportedControlProtocolVersion,
+    HAILO_UNSUPPORTED_FW_VERSION => UnsupportedFwVersion,
+    HAILO_INVALID_CONTROL_RESPONSE => InvalidControlResponse,
+    HAILO_FW_CONTROL_FAILURE => FwControlFailure,
+    HAILO_ETH_FAILURE => EthFailure,
+    HAILO_ETH_INTERFACE_NOT_FOUND => EthInterfaceNotFound,
+    HAILO_ETH_RECV_FAILURE => EthRecvFailure,
+    HAILO_ETH_SEND_FAILURE => EthSendFailure,
+    HAILO_INVALID_FIRMWARE => InvalidFirmware,
+    HAILO_INVALID_CONTEXT_COUNT => InvalidContextCount,
+    HAILO_INVALID_FRAME => InvalidFrame,
+    HAILO_INVALID_HEF => InvalidHef,
+    HAILO_PCIE_NOT_SUPPORTED_ON_PLATFORM => PcieNotSupportedOnPlatform,
+    HAILO_INTERRUPTED_BY_SIGNAL => InterruptedBySignal,
+    HAILO_START_VDMA_CHANNEL_FAIL => StartVdmaChannelFail,
+    HAILO_SYNC_VDMA_BUFFER_FAIL => SyncVdmaBufferFail,
+    HAILO_STOP_VDMA_CHANNEL_FAIL => StopVdmaChannelFail,
+    HAILO_CLOSE_VDMA_CHANNEL_FAIL => CloseVdmaChannelFail,
+    HAILO_ATR_TABLES_CONF_VALIDATION_FAIL => AtrTablesConfValidationFail,
+    HAILO_EVENT_CREATE_FAIL => EventCreateFail,
+    HAILO_READ_EVENT_FAIL => ReadEventFail,
+    HAILO_DRIVER_OPERATION_FAILED => DriverOperationFailed,
+    HAILO_INVALID_FIRMWARE_MAGIC => InvalidFirmwareMagic,
+    HAILO_INVALID_FIRMWARE_CODE_SIZE => InvalidFirmwareCodeSize,
+    HAILO_INVALID_KEY_CERTIFICATE_SIZE => InvalidKeyCertificateSize,
+    HAILO_INVALID_CONTENT_CERTIFICATE_SIZE => InvalidContentCertificateSize,
+    HAILO_MISMATCHING_FIRMWARE_BUFFER_SIZES => MismatchingFirmwareBufferSizes,
+    HAILO_INVALID_FIRMWARE_CPU_ID => InvalidFirmwareCpuId,
+    HAILO_CONTROL_RESPONSE_MD5_MISMATCH => ControlResponseMd5Mismatch,
+    HAILO_GET_CONTROL_RESPONSE_FAIL => GetControlResponseFail,
+    HAILO_GET_D2H_EVENT_MESSAGE_FAIL => GetD2hEventMessageFail,
+    HAILO_MUTEX_INIT_FAIL => MutexInitFail,
+    HAILO_OUT_OF_DESCRIPTORS => OutOfDescriptors,
+    HAILO_UNSUPPORTED_OPCODE => UnsupportedOpcode,
+    HAILO_USER_MODE_RATE_LIMITER_NOT_SUPPORTED => UserModeRateLimiterNotSupported,
+    HAILO_RATE_LIMIT_MAXIMUM_BANDWIDTH_EXCEEDED => RateLimitMaximumBandwidthExceeded,
+    HAILO_ANSI_TO_UTF16_CONVERSION_FAILED => AnsiToUtf16ConversionFailed,
+    HAILO_UTF16_TO_ANSI_CONVERSION_FAILED => Utf16ToAnsiConversionFailed,
+    HAILO_UNEXPECTED_INTERFACE_INFO_FAILURE => UnexpectedInterfaceInfoFailure,
+    HAILO_UNEXPECTED_ARP_TABLE_FAILURE => UnexpectedArpTableFailure,
+    HAILO_MAC_ADDRESS_NOT_FOUND => MacAddressNotFound,
+    HAILO_NO_IPV4_INTERFACES_FOUND => NoIpv4InterfacesFound,
+    HAILO_SHUTDOWN_EVENT_SIGNALED => ShutdownEventSignaled,
+    HAILO_THREAD_ALREADY_ACTIVATED => ThreadAlreadyActivated,
+    HAILO_THREAD_NOT_ACTIVATED => ThreadNotActivated,
+    HAILO_THREAD_NOT_JOINABLE => ThreadNotJoinable,
+    HAILO_NOT_FOUND => NotFound,
+    HAILO_COMMUNICATION_CLOSED => CommunicationClosed,
+    HAILO_STREAM_ABORT => StreamAbort,
+    HAILO_DRIVER_NOT_INSTALLED => DriverNotInstalled,
+    HAILO_NOT_AVAILABLE => NotAvailable,
+    HAILO_TRAFFIC_CONTROL_FAILURE => TrafficControlFailure,
+    HAILO_INVALID_SECOND_STAGE => InvalidSecondStage,
+    HAILO_INVALID_PIPELINE => InvalidPipeline,
+    HAILO_NETWORK_GROUP_NOT_ACTIVATED => NetworkGroupNotActivated,
+    HAILO_VSTREAM_PIPELINE_NOT_ACTIVATED => VstreamPipelineNotActivated,
+    HAILO_OUT_OF_FW_MEMORY => OutOfFwMemory,
+    HAILO_STREAM_NOT_ACTIVATED => StreamNotActivated,
+    HAILO_DEVICE_IN_USE => DeviceInUse,
+    HAILO_OUT_OF_PHYSICAL_DEVICES => OutOfPhysicalDevices,
+    HAILO_INVALID_DEVICE_ARCHITECTURE => InvalidDeviceArchitecture,
+    HAILO_INVALID_DRIVER_VERSION => InvalidDriverVersion,
+    HAILO_RPC_FAILED => RpcFailed,
+    HAILO_INVALID_SERVICE_VERSION => InvalidServiceVersion,
+    HAILO_NOT_SUPPORTED => NotSupported,
+    HAILO_NMS_BURST_INVALID_DATA => NmsBurstInvalidData,
+    HAILO_OUT_OF_HOST_CMA_MEMORY => OutOfHostCmaMemory,
+    HAILO_QUEUE_IS_FULL => QueueIsFull,
+    HAILO_DMA_MAPPING_ALREADY_EXISTS => DmaMappingAlreadyExists,
+    HAILO_CANT_MEET_BUFFER_REQUIREMENTS => CantMeetBufferRequirements,
+    HAILO_DRIVER_INVALID_RESPONSE => DriverInvalidResponse,
+    HAILO_DRIVER_INVALID_IOCTL => DriverInvalidIoctl,
+    HAILO_DRIVER_TIMEOUT => DriverTimeout,
+    HAILO_DRIVER_INTERRUPTED => DriverInterrupted,
+    HAILO_CONNECTION_REFUSED => ConnectionRefused,
+    HAILO_DRIVER_WAIT_CANCELED => DriverWaitCanceled,
+    HAILO_HEF_FILE_CORRUPTED => HefFileCorrupted,
+    HAILO_HEF_NOT_SUPPORTED => HefNotSupported,
+    HAILO_HEF_NOT_COMPATIBLE_WITH_DEVICE => HefNotCompatibleWithDevice,
+    HAILO_INVALID_HEF_USE => InvalidHefUse,
+    HAILO_OPERATION_ABORTED => OperationAborted,
+    HAILO_DEVICE_NOT_CONNECTED => DeviceNotConnected,
+    HAILO_DEVICE_TEMPORARILY_UNAVAILABLE => DeviceTemporarilyUnavailable,
+}
+
+impl HailoError {
+    /// The operation did not complete in time.
+    pub fn is_timeout(self) -> bool {
+        matches!(self, Self::Timeout | Self::DriverTimeout)
+    }
+
+    /// The device went away; the handle that produced this error should be
+    /// released and the device re-opened.
+    pub fn is_device_lost(self) -> bool {
+        matches!(
+            self,
+            Self::DeviceNotConnected | Self::CommunicationClosed | Self::ConnectionRefused
+        )
+    }
+
+    /// Transient condition: the same call may succeed if repeated later.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            Self::Timeout
+                | Self::DriverTimeout
+                | Self::QueueIsFull
+                | Self::DeviceInUse
+                | Self::DeviceTemporarilyUnavailable
+                | Self::OutOfPhysicalDevices
+                | Self::InterruptedBySignal
+                | Self::DriverInterrupted
+        )
+    }
+
+    /// Reported by, or about, the Hailo PCIe/integrated kernel driver.
+    pub fn is_driver_error(self) -> bool {
+        matches!(
+            self,
+            Self::DriverOperationFailed
+                | Self::DriverNotInstalled
+                | Self::InvalidDriverVersion
+                | Self::DriverInvalidResponse
+                | Self::DriverInvalidIoctl
+                | Self::DriverTimeout
+                | Self::DriverInterrupted
+                | Self::DriverWaitCanceled
+        )
+    }
+
+    /// The library's description of this status, if it provides one.
+    pub fn message(self) -> Option<String> {
+        let msg = unsafe { hailo_get_status_message(self.status()) };
+        if msg.is_null() {
+            return None;
+        }
+        let msg = unsafe { CStr::from_ptr(msg) };
+        Some(msg.to_string_lossy().into_owned())
+    }
+}
+
+impl fmt::Display for HailoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name(), self.status())?;
+        if let Some(msg) = self.message() {
+            write!(f, ": {msg}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for HailoError {}
+
+impl From<HailoError> for hailo_status {
+    fn from(err: HailoError) -> Self {
+        err.status()
+    }
+}
+
+/// Converts a raw return code into `Ok(())` or the matching [`HailoError`].
+pub fn check(status: hailo_status) -> HailoResult<()> {
+    match HailoError::from_status(status) {
+        None => Ok(()),
+        Some(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn success_is_ok() {
+        assert_eq!(check(HAILO_SUCCESS), Ok(()));
+        assert_eq!(HailoError::from_status(HAILO_SUCCESS), None);
+    }
+
+    #[test]
+    fn every_named_status_round_trips() {
+        for status in 1..=HAILO_DEVICE_TEMPORARILY_UNAVAILABLE {
+            let err = HailoError::from_status(status).unwrap();
+            assert!(
+                !matches!(err, HailoError::Unknown(_)),
+                "status {status} unnamed"
+            );
+            assert_eq!(err.status(), status);
+            assert_eq!(check(status), Err(err));
+        }
+    }
+
+    #[test]
+    fn names_match_constants() {
+        assert_eq!(HailoError::Timeout.name(), "HAILO_TIMEOUT");
+        assert_eq!(HailoError::InvalidHef.name(), "HAILO_INVALID_HEF");
+        assert_eq!(
+            HailoError::DeviceTemporarilyUnavailable.name(),
+            "HAILO_DEVICE_TEMPORARILY_UNAVAILABLE"
+        );
+    }
+
+    #[test]
+    fn unknown_status_is_preserved() {
+        let err = HailoError::from_status(1234).unwrap();
+        assert_eq!(err, HailoError::Unknown(1234));
+        assert_eq!(err.status(), 1234);
+        assert_eq!(hailo_status::from(err), 1234);
+    }
+
+    #[test]
+    fn classification() {
+        assert!(HailoError::Timeout.is_timeout());
+        assert!(HailoError::DriverTimeout.is_timeout());
+        assert!(!HailoError::InvalidArgument.is_timeout());
+
+        assert!(HailoError::DeviceNotConnected.is_device_lost());
+        assert!(!HailoError::Timeout.is_device_lost());
+
+        assert!(HailoError::QueueIsFull.is_retryable());
+        assert!(HailoError::DeviceInUse.is_retryable());
+        assert!(!HailoError::InvalidHef.is_retryable());
+
+        assert!(HailoError::DriverInvalidIoctl.is_driver_error());
+        assert!(!HailoError::InvalidArgument.is_driver_error());
+    }
+
+    #[test]
+    fn display_starts_with_name_and_code() {
+        assert!(
+            HailoError::Timeout
+                .to_string()
+                .starts_with("HAILO_TIMEOUT (4)")
+        );
+        assert!(
+            HailoError::Unknown(500)
+                .to_string()
+                .starts_with("HAILO_UNKNOWN_STATUS (500)")
+        );
+    }
+}
@@ -7,8 +7,8 @@
 use std::os::raw::{c_char, c_void};
 
 use crate::enums::{
-    hailo_averaging_factor_t, hailo_cpu_id_t, hailo_device_type_t, hailo_dvm_options_t,
-    hailo_fw_logger_level_t,
+    hailo_averaging_factor_t, hailo_cpu_id_t, hailo_device_type_t,
+    hailo_dma_buffer_direction_t, hailo_dvm_options_t, hailo_format_type_t, hailo_fw_logger_level_t,
     hailo_measurement_buffer_index_t, hailo_notification_id_t,
     hailo_power_measurement_types_t, hailo_reset_device_mode_t, hailo_sampling_period_t,
     hailo_sensor_types_t, hailo_stream_direction_t, hailo_watchdog_mode_t,
@@ -24,10 +24,10 @@ use crate::types::{
     float32_t, hailo_activate_network_group_params_t, hailo_chip_temperature_info_t,
     hailo_configure_params_t, hailo_core_information_t, hailo_demux_params_t,
     hailo_device_id_t, hailo_device_identity_t, hailo_extended_device_information_t,
-    hailo_format_t, hailo_health_info_t, hailo_health_stats_t,
+    hailo_3d_image_shape_t, hailo_format_t, hailo_health_info_t, hailo_health_stats_t,
     hailo_i2c_slave_config_t, hailo_input_vstream_params_by_name_t,
-    hailo_latency_measurement_result_t, hailo_network_group_info_t, hailo_network_info_t,
-    hailo_notification_callback, hailo_output_vstream_params_by_name_t,
+    hailo_latency_measurement_result_t, hailo_network_group_info_t,
+    hailo_network_info_t, hailo_nms_fuse_input_t, hailo_notification_callback, hailo_output_vstream_params_by_name_t,
     hailo_pcie_device_info_t, hailo_performance_stats_t, hailo_power_measurement_data_t,
     hailo_quant_info_t, hailo_stream_info_t, hailo_stream_read_async_callback_t,
     hailo_stream_write_async_callback_t, hailo_transform_params_t, hailo_version_t,
@@ -464,6 +464,36 @@ unsafe extern "C" {
         opaque: *mut c_void,
     ) -> hailo_status;
 
+    // --- DMA buffer mapping -------------------------------------------------
+
+    pub fn hailo_device_dma_map_buffer(
+        device: hailo_device,
+        address: *mut c_void,
+        size: usize,
+        direction: hailo_dma_buffer_direction_t,
+    ) -> hailo_status;
+
+    pub fn hailo_device_dma_unmap_buffer(
+        device: hailo_device,
+        address: *mut c_void,
+        size: usize,
+        direction: hailo_dma_buffer_direction_t,
+    ) -> hailo_status;
+
+    pub fn hailo_vdevice_dma_map_buffer(
+        vdevice: hailo_vdevice,
+        address: *mut c_void,
+        size: usize,
+        direction: hailo_dma_buffer_direction_t,
+    ) -> hailo_status;
+
+    pub fn hailo_vdevice_dma_unmap_buffer(
+        vdevice: hailo_vdevice,
+        address: *mut c_void,
+        size: usize,
+        direction: hailo_dma_buffer_direction_t,
+    ) -> hailo_status;
+
     // --- Quantisation info --------------------------------------------------
 
     pub fn hailo_get_output_stream_quant_infos(
@@ -480,18 +510,68 @@ unsafe extern "C" {
 
     // --- Transform contexts -------------------------------------------------
 
+    pub fn hailo_is_input_transformation_required2(
+        src_image_shape: *const hailo_3d_image_shape_t,
+        src_format: *const hailo_format_t,
+        dst_image_shape: *const hailo_3d_image_shape_t,
+        dst_format: *const hailo_format_t,
+        quant_infos: *const hailo_quant_info_t,
+        quant_infos_count: usize,
+        transformation_required: *mut bool,
+    ) -> hailo_status;
+
+    pub fn hailo_is_output_transformation_required2(
+        src_image_shape: *const hailo_3d_image_shape_t,
+        src_format: *const hailo_format_t,
+        dst_image_shape: *const hailo_3d_image_shape_t,
+        dst_format: *const hailo_format_t,
+        quant_infos: *const hailo_quant_info_t,
+        quant_infos_count: usize,
+        transformation_required: *mut bool,
+    ) -> hailo_status;
+
+    /// Host-only: needs no device, only the `hailo_stream_info_t` from the HEF.
     pub fn hailo_create_input_transform_context(
+        stream_info: *const hailo_stream_info_t,
+        transform_params: *const hailo_transform_params_t,
+        context: *mut hailo_input_transform_context,
+    ) -> hailo_status;
+
+    pub fn hailo_create_input_transform_context_by_stream(
         stream: hailo_input_stream,
         transform_params: *const hailo_transform_params_t,
         context: *mut hailo_input_transform_context,
     ) -> hailo_status;
 
+    /// Host-only: needs no device, only the `hailo_stream_info_t` from the HEF.
     pub fn hailo_create_output_transform_context(
+        stream_info: *const hailo_stream_info_t,
+        transform_params: *const hailo_transform_params_t,
+        context: *mut hailo_output_transform_context,
+    ) -> hailo_status;
+
+    pub fn hailo_create_output_transform_context_by_stream(
         stream: hailo_output_stream,
         transform_params: *const hailo_transform_params_t,
         context: *mut hailo_output_transform_context,
     ) -> hailo_status;
 
+    pub fn hailo_transform_frame_by_input_transform_context(
+        context: hailo_input_transform_context,
+        src: *const c_void,
+        src_size: usize,
+        dst: *mut c_void,
+        dst_size: usize,
+    ) -> hailo_status;
+
+    pub fn hailo_transform_frame_by_output_transform_context(
+        context: hailo_output_transform_context,
+        src: *const c_void,
+        src_size: usize,
+        dst: *mut c_void,
+        dst_size: usize,
+    ) -> hailo_status;
+
     pub fn hailo_input_transform_context_write(
         context: hailo_input_transform_context,
         buffer: *const c_void,
@@ -512,6 +592,18 @@ unsafe extern "C" {
         context: hailo_output_transform_context,
     ) -> hailo_status;
 
+    pub fn hailo_is_qp_valid(
+        quant_info: hailo_quant_info_t,
+        is_qp_valid: *mut bool,
+    ) -> hailo_status;
+
+    pub fn hailo_fuse_nms_frames(
+        nms_fuse_inputs: *const hailo_nms_fuse_input_t,
+        inputs_count: u32,
+        fused_buffer: *mut u8,
+        fused_buffer_size: usize,
+    ) -> hailo_status;
+
     // --- Output demuxer -----------------------------------------------------
 
     pub fn hailo_create_output_demuxer(
@@ -581,8 +673,44 @@ unsafe extern "C" {
 
     pub fn hailo_input_vstream_clear(vstream: hailo_input_vstream) -> hailo_status;
 
+    pub fn hailo_get_input_vstream_frame_size(
+        vstream: hailo_input_vstream,
+        frame_size: *mut usize,
+    ) -> hailo_status;
+
+    pub fn hailo_get_output_vstream_frame_size(
+        vstream: hailo_output_vstream,
+        frame_size: *mut usize,
+    ) -> hailo_status;
+
+    pub fn hailo_get_input_vstream_user_format(
+        vstream: hailo_input_vstream,
+        user_buffer_format: *mut hailo_format_t,
+    ) -> hailo_status;
+
+    pub fn hailo_get_output_vstream_user_format(
+        vstream: hailo_output_vstream,
+        user_buffer_format: *mut hailo_format_t,
+    ) -> hailo_status;
+
     // --- Default parameter initialisation -----------------------------------
 
+    pub fn hailo_make_input_vstream_params(
+        network_group: hailo_configured_network_group,
+        unused: bool,
+        format_type: hailo_format_type_t,
+        input_params: *mut hailo_input_vstream_params_by_name_t,
+        input_params_count: *mut usize,
+    ) -> hailo_status;
+
+    pub fn hailo_make_output_vstream_params(
+        network_group: hailo_configured_network_group,
+        unused: bool,
+        format_type: hailo_format_type_t,
+        output_params: *mut hailo_output_vstream_params_by_name_t,
+        output_params_count: *mut usize,
+    ) -> hailo_status;
+
     pub fn hailo_get_default_vstream_params(
         vstream_info: *const hailo_vstream_info_t,
         user_buffer_format: hailo_format_t,
@@ -611,8 +739,9 @@ unsafe extern "C" {
 mod tests {
     use super::*;
     use crate::enums::{
-        hailo_averaging_factor_t, hailo_cpu_id_t, hailo_device_type_t, hailo_dvm_options_t,
-        hailo_fw_logger_level_t, hailo_measurement_buffer_index_t, hailo_notification_id_t,
+        hailo_averaging_factor_t, hailo_cpu_id_t, hailo_device_type_t,
+        hailo_dma_buffer_direction_t, hailo_dvm_options_t, hailo_format_type_t, hailo_fw_logger_level_t,
+        hailo_measurement_buffer_index_t, hailo_notification_id_t,
         hailo_power_measurement_types_t, hailo_reset_device_mode_t, hailo_sampling_period_t,
         hailo_sensor_types_t, hailo_stream_direction_t, hailo_watchdog_mode_t,
     };
@@ -627,9 +756,10 @@ mod tests {
         float32_t, hailo_activate_network_group_params_t, hailo_chip_temperature_info_t,
         hailo_configure_params_t, hailo_core_information_t, hailo_demux_params_t,
         hailo_device_id_t, hailo_device_identity_t, hailo_extended_device_information_t,
-        hailo_format_t, hailo_health_info_t, hailo_health_stats_t, hailo_i2c_slave_config_t,
-        hailo_input_vstream_params_by_name_t, hailo_latency_measurement_result_t,
-        hailo_network_group_info_t, hailo_network_info_t, hailo_notification_callback,
+        hailo_3d_image_shape_t, hailo_format_t, hailo_health_info_t, hailo_health_stats_t,
+        hailo_i2c_slave_config_t, hailo_input_vstream_params_by_name_t,
+        hailo_latency_measurement_result_t, hailo_network_group_info_t,
+        hailo_network_info_t, hailo_nms_fuse_input_t, hailo_notification_callback,
         hailo_output_vstream_params_by_name_t, hailo_pcie_device_info_t,
         hailo_performance_stats_t, hailo_power_measurement_data_t, hailo_quant_info_t,
         hailo_stream_info_t, hailo_stream_read_async_callback_t,
@@ -904,7 +1034,7 @@ mod tests {
             *const c_char,
             *mut hailo_vstream_info_t,
             *mut usize,
-        ) -> hailo_status = hailo_hef_get_vstream_infos;
+        ) -> hailo_status = hailo_hef_get_all_vstream_infos;
         let _: unsafe extern "C" fn(
             hailo_hef,
             *mut hailo_network_group_info_t,
@@ -949,7 +1079,7 @@ mod tests {
         let _: unsafe extern "C" fn(hailo_activated_network_group) -> hailo_status =
             hailo_deactivate_network_group;
         let _: unsafe extern "C" fn(hailo_configured_network_group) -> hailo_status =
-            hailo_release_network_group;
+            hailo_shutdown_network_group;
         let _: unsafe extern "C" fn(
             hailo_configured_network_group,
             *mut hailo_network_group_info_t,
@@ -1002,6 +1132,34 @@ mod tests {
         ) -> hailo_status = hailo_output_stream_read_async;
     }
 
+    #[test]
+    fn dma_mapping_signatures() {
+        let _: unsafe extern "C" fn(
+            hailo_device,
+            *mut c_void,
+            usize,
+            hailo_dma_buffer_direction_t,
+        ) -> hailo_status = hailo_device_dma_map_buffer;
+        let _: unsafe extern "C" fn(
+            hailo_device,
+            *mut c_void,
+            usize,
+            hailo_dma_buffer_direction_t,
+        ) -> hailo_status = hailo_device_dma_unmap_buffer;
+        let _: unsafe extern "C" fn(
+            hailo_vdevice,
+            *mut c_void,
+            usize,
+            hailo_dma_buffer_direction_t,
+        ) -> hailo_status = hailo_vdevice_dma_map_buffer;
+        let _: unsafe extern "C" fn(
+            hailo_vdevice,
+            *mut c_void,
+            usize,
+            hailo_dma_buffer_direction_t,
+        ) -> hailo_status = hailo_vdevice_dma_unmap_buffer;
+    }
+
     #[test]
     fn quant_info_signatures() {
         let _: unsafe extern "C" fn(
@@ -1019,15 +1177,57 @@ mod tests {
     #[test]
     fn transform_context_signatures() {
         let _: unsafe extern "C" fn(
-            hailo_input_stream,
+            *const hailo_3d_image_shape_t,
+            *const hailo_format_t,
+            *const hailo_3d_image_shape_t,
+            *const hailo_format_t,
+            *const hailo_quant_info_t,
+            usize,
+            *mut bool,
+        ) -> hailo_status = hailo_is_input_transformation_required2;
+        let _: unsafe extern "C" fn(
+            *const hailo_3d_image_shape_t,
+            *const hailo_format_t,
+            *const hailo_3d_image_shape_t,
+            *const hailo_format_t,
+            *const hailo_quant_info_t,
+            usize,
+            *mut bool,
+        ) -> hailo_status = hailo_is_output_transformation_required2;
+        let _: unsafe extern "C" fn(
+            *const hailo_stream_info_t,
             *const hailo_transform_params_t,
             *mut hailo_input_transform_context,
         ) -> hailo_status = hailo_create_input_transform_context;
         let _: unsafe extern "C" fn(
-            hailo_output_stream,
+            hailo_input_stream,
+            *const hailo_transform_params_t,
+            *mut hailo_input_transform_context,
+        ) -> hailo_status = hailo_create_input_transform_context_by_stream;
+        let _: unsafe extern "C" fn(
+            *const hailo_stream_info_t,
             *const hailo_transform_params_t,
             *mut hailo_output_transform_context,
         ) -> hailo_status = hailo_create_output_transform_context;
+        let _: unsafe extern "C" fn(
+            hailo_output_stream,
+            *const hailo_transform_params_t,
+            *mut hailo_output_transform_context,
+        ) -> hailo_status = hailo_create_output_transform_context_by_stream;
+        let _: unsafe extern "C" fn(
+            hailo_input_transform_context,
+            *const c_void,
+            usize,
+            *mut c_void,
+            usize,
+        ) -> hailo_status = hailo_transform_frame_by_input_transform_context;
+        let _: unsafe extern "C" fn(
+            hailo_output_transform_context,
+            *const c_void,
+            usize,
+            *mut c_void,
+            usize,
+        ) -> hailo_status = hailo_transform_frame_by_output_transform_context;
         let _: unsafe extern "C" fn(
             hailo_input_transform_context,
             *const c_void,
@@ -1042,6 +1242,14 @@ mod tests {
             hailo_release_input_transform_context;
         let _: unsafe extern "C" fn(hailo_output_transform_context) -> hailo_status =
             hailo_release_output_transform_context;
+        let _: unsafe extern "C" fn(hailo_quant_info_t, *mut bool) -> hailo_status =
+            hailo_is_qp_valid;
+        let _: unsafe extern "C" fn(
+            *const hailo_nms_fuse_input_t,
+            u32,
+            *mut u8,
+            usize,
+        ) -> hailo_status = hailo_fuse_nms_frames;
     }
 
     #[test]
@@ -1095,6 +1303,14 @@ mod tests {
             hailo_input_vstream_flush;
         let _: unsafe extern "C" fn(hailo_input_vstream) -> hailo_status =
             hailo_input_vstream_clear;
+        let _: unsafe extern "C" fn(hailo_input_vstream, *mut usize) -> hailo_status =
+            hailo_get_input_vstream_frame_size;
+        let _: unsafe extern "C" fn(hailo_output_vstream, *mut usize) -> hailo_status =
+            hailo_get_output_vstream_frame_size;
+        let _: unsafe extern "C" fn(hailo_input_vstream, *mut hailo_format_t) -> hailo_status =
+            hailo_get_input_vstream_user_format;
+        let _: unsafe extern "C" fn(hailo_output_vstream, *mut hailo_format_t) -> hailo_status =
+            hailo_get_output_vstream_user_format;
     }
 
     #[test]
@@ -1105,6 +1321,20 @@ mod tests {
             hailo_stream_direction_t,
             *mut hailo_vstream_params_t,
         ) -> hailo_status = hailo_get_default_vstream_params;
+        let _: unsafe extern "C" fn(
+            hailo_configured_network_group,
+            bool,
+            hailo_format_type_t,
+            *mut hailo_input_vstream_params_by_name_t,
+            *mut usize,
+        ) -> hailo_status = hailo_make_input_vstream_params;
+        let _: unsafe extern "C" fn(
+            hailo_configured_network_group,
+            bool,
+            hailo_format_type_t,
+            *mut hailo_output_vstream_params_by_name_t,
+            *mut usize,
+        ) -> hailo_status = hailo_make_output_vstream_params;
         let _: unsafe extern "C" fn(
             hailo_input_vstream,
             *mut hailo_latency_measurement_result_t,
@@ -0,0 +1,547 @@
+//! Validated firmware and second-stage updates.
+//!
+//! A wrong image can brick a board, so [`FirmwareImage`] parses the headers
+//! the firmware itself checks (magic, code size, certificate sizes, CPU
+//! sections) before anything is sent, failing with the matching
+//! `HAILO_INVALID_FIRMWARE_*` error.  [`FirmwareUpdate`] then compares the
+//! image version with the running firmware, performs the update and can
+//! reset and re-identify the device to confirm it, reporting each
+//! [`UpdateStage`] as it starts.  Once the image is sent the update always
+//! returns a [`FirmwareUpdateReport`]; a failed reset or re-identification
+//! is recorded in it rather than returned as an error.
+//!
+//! Image layout (all fields little-endian `u32`), mirroring
+//! `firmware_header.h`:
+//!
+//! ```text
+//! firmware:     [app header][app code][cert header][key][content][core header][core code]
+//! second stage: [header][code][cert header][key][content]
+//! header:       magic, header_version, major, minor, revision, code_size
+//! cert header:  key_size, content_size
+//! ```
+
+use std::ffi::c_void;
+use std::fmt;
+use std::path::Path;
+
+use crate::device::{Device, Version};
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{hailo_reset_device, hailo_update_firmware, hailo_update_second_stage};
+use crate::typed_enums::{DeviceArchitecture, ResetDeviceMode};
+use crate::watchdog::CpuId;
+
+const FIRMWARE_HEADER_VERSION_INITIAL: u32 = 0;
+const MAX_APP_CODE_SIZE: u32 = 0x40000;
+const MAX_CORE_CODE_SIZE: u32 = 0x18000;
+const MAX_SECOND_STAGE_CODE_SIZE: u32 = 0x80000;
+const MAX_CERT_KEY_SIZE: u32 = 0x1000;
+const MAX_CERT_CONTENT_SIZE: u32 = 0x1000;
+
+/// Chip family an image is built for, identified by its header magic.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FirmwareFamily {
+    Hailo8,
+    Hailo15,
+    Pluto,
+}
+
+impl FirmwareFamily {
+    pub fn from_magic(magic: u32) -> Option<Self> {
+        match magic {
+            0x1DD8_9DE0 => Some(Self::Hailo8),
+            0xE905_DAAB => Some(Self::Hailo15),
+            0xF947_39AB => Some(Self::Pluto),
+            _ => None,
+        }
+    }
+
+    /// Family expected by a device architecture, if this crate knows it.
+    pub fn for_architecture(arch: DeviceArchitecture) -> Option<Self> {
+        match arch {
+            DeviceArchitecture::Hailo8A0
+            | DeviceArchitecture::Hailo8
+            | DeviceArchitecture::Hailo8L => Some(Self::Hailo8),
+            DeviceArchitecture::Hailo15H
+            | DeviceArchitecture::Hailo15M
+            | DeviceArchitecture::Hailo10H => Some(Self::Hailo15),
+            DeviceArchitecture::Hailo15L => Some(Self::Pluto),
+            _ => None,
+        }
+    }
+}
+
+/// Which update call an image is meant for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ImageKind {
+    /// `hailo_update_firmware`: app and core CPU firmware.
+    Firmware,
+    /// `hailo_update_second_stage`: the second-stage boot loader.
+    SecondStage,
+}
+
+impl fmt::Display for ImageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Firmware => f.write_str("firmware"),
+            Self::SecondStage => f.write_str("second stage"),
+        }
+    }
+}
+
+/// One CPU's code section.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FirmwareSection {
+    /// [`CpuId::Cpu0`] for the app CPU, [`CpuId::Cpu1`] for the core CPU.
+    pub cpu: CpuId,
+    pub version: Version,
+    pub code_size: u32,
+}
+
+/// A parsed and validated update image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirmwareImage {
+    pub kind: ImageKind,
+    pub family: FirmwareFamily,
+    pub version: Version,
+    pub sections: Vec<FirmwareSection>,
+    pub key_certificate_size: u32,
+    pub content_certificate_size: u32,
+    bytes: Vec<u8>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn u32(&mut self) -> HailoResult<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn take(&mut self, len: usize) -> HailoResult<&[u8]> {
+        let bytes = self
+            .offset
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(self.offset..end))
+            .ok_or(HailoError::MismatchingFirmwareBufferSizes)?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.offset == self.bytes.len()
+    }
+
+    fn section(
+        &mut self,
+        cpu: CpuId,
+        max_code_size: u32,
+    ) -> HailoResult<(FirmwareFamily, FirmwareSection)> {
+        let family =
+            FirmwareFamily::from_magic(self.u32()?).ok_or(HailoError::InvalidFirmwareMagic)?;
+        if self.u32()? != FIRMWARE_HEADER_VERSION_INITIAL {
+            return Err(HailoError::InvalidFirmware);
+        }
+        let version = Version {
+            major: self.u32()?,
+            minor: self.u32()?,
+            revision: self.u32()?,
+        };
+        let code_size = self.u32()?;
+        if code_size > max_code_size {
+            return Err(HailoError::InvalidFirmwareCodeSize);
+        }
+        self.take(code_size as usize)?;
+        Ok((
+            family,
+            FirmwareSection {
+                cpu,
+                version,
+                code_size,
+            },
+        ))
+    }
+
+    fn certificate(&mut self) -> HailoResult<(u32, u32)> {
+        let key_size = self.u32()?;
+        let content_size = self.u32()?;
+        if key_size > MAX_CERT_KEY_SIZE {
+            return Err(HailoError::InvalidKeyCertificateSize);
+        }
+        if content_size > MAX_CERT_CONTENT_SIZE {
+            return Err(HailoError::InvalidContentCertificateSize);
+        }
+        self.take(key_size as usize)?;
+        self.take(content_size as usize)?;
+        Ok((key_size, content_size))
+    }
+}
+
+impl FirmwareImage {
+    /// Validates a firmware image (app + core CPU).
+    pub fn firmware(bytes: Vec<u8>) -> HailoResult<Self> {
+        let mut reader = Reader {
+            bytes: &bytes,
+            offset: 0,
+        };
+        let (family, app) = reader.section(CpuId::Cpu0, MAX_APP_CODE_SIZE)?;
+        let (key_certificate_size, content_certificate_size) = reader.certificate()?;
+        if reader.is_empty() {
+            // Only the app CPU section is present.
+            return Err(HailoError::InvalidFirmwareCpuId);
+        }
+        let (core_family, core) = reader.section(CpuId::Cpu1, MAX_CORE_CODE_SIZE)?;
+        if !reader.is_empty() {
+            return Err(HailoError::MismatchingFirmwareBufferSizes);
+        }
+        if core_family != family || core.version != app.version {
+            return Err(HailoError::InvalidFirmware);
+        }
+        Ok(Self {
+            kind: ImageKind::Firmware,
+            family,
+            version: app.version,
+            sections: vec![app, core],
+            key_certificate_size,
+            content_certificate_size,
+            bytes,
+        })
+    }
+
+    /// Validates a second-stage boot loader image.
+    pub fn second_stage(bytes: Vec<u8>) -> HailoResult<Self> {
+        let mut reader = Reader {
+            bytes: &bytes,
+            offset: 0,
+        };
+        let (family, section) = reader.section(CpuId::Cpu0, MAX_SECOND_STAGE_CODE_SIZE)?;
+        let (key_certificate_size, content_certificate_size) = reader.certificate()?;
+        if !reader.is_empty() {
+            return Err(HailoError::MismatchingFirmwareBufferSizes);
+        }
+        Ok(Self {
+            kind: ImageKind::SecondStage,
+            family,
+            version: section.version,
+            sections: vec![section],
+            key_certificate_size,
+            content_certificate_size,
+            bytes,
+        })
+    }
+
+    /// Reads and validates an image of `kind` from `path`.
+    pub fn from_file(kind: ImageKind, path: impl AsRef<Path>) -> HailoResult<Self> {
+        let bytes = std::fs::read(path).map_err(|_| HailoError::OpenFileFailure)?;
+        match kind {
+            ImageKind::Firmware => Self::firmware(bytes),
+            ImageKind::SecondStage => Self::second_stage(bytes),
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Update flow
+// ---------------------------------------------------------------------------
+
+/// What [`FirmwareUpdate::run`] did with the image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UpdateAction {
+    Updated,
+    /// The device already runs this version and `force` was not set.
+    SkippedSameVersion,
+}
+
+/// Step of [`FirmwareUpdate::run_with_progress`] about to start.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum UpdateStage {
+    /// Reading the running firmware version and chip family.
+    Identifying,
+    /// Sending the image to the device.
+    Flashing,
+    Resetting,
+    /// Re-identifying the device after the reset.
+    Confirming,
+}
+
+/// Outcome of [`FirmwareUpdate::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirmwareUpdateReport {
+    pub kind: ImageKind,
+    pub image_version: Version,
+    /// Firmware version reported by the device before the update.
+    pub previous_version: Version,
+    pub action: UpdateAction,
+    /// Reset mode applied after the update, if any.
+    pub reset: Option<ResetDeviceMode>,
+    /// Firmware version reported after the reset, or why the reset or
+    /// re-identification failed.  `None` if no reset was requested.
+    pub confirmation: Option<Result<Version, HailoError>>,
+}
+
+impl FirmwareUpdateReport {
+    /// Whether the device was re-identified and runs the image's version.
+    /// Always `false` for second-stage updates, whose version the device
+    /// does not report.
+    pub fn is_confirmed(&self) -> bool {
+        self.kind == ImageKind::Firmware && self.confirmation == Some(Ok(self.image_version))
+    }
+}
+
+/// Update of one validated image.  By default refuses downgrades, skips an
+/// image matching the running firmware and does not reset the device.
+#[derive(Debug, Clone)]
+pub struct FirmwareUpdate {
+    image: FirmwareImage,
+    allow_downgrade: bool,
+    force: bool,
+    reset: Option<ResetDeviceMode>,
+}
+
+impl FirmwareUpdate {
+    pub fn new(image: FirmwareImage) -> Self {
+        Self {
+            image,
+            allow_downgrade: false,
+            force: false,
+            reset: None,
+        }
+    }
+
+    pub fn allow_downgrade(mut self, allow: bool) -> Self {
+        self.allow_downgrade = allow;
+        self
+    }
+
+    /// Update even when the versions already match.
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Reset with `mode` after updating, then re-identify the device.
+    pub fn reset(mut self, mode: ResetDeviceMode) -> Self {
+        self.reset = Some(mode);
+        self
+    }
+
+    /// Decides whether to send the image.  Only firmware images are compared
+    /// with the running version.
+    fn plan(&self, current: Version) -> HailoResult<UpdateAction> {
+        if self.image.kind == ImageKind::SecondStage || self.force {
+            return Ok(UpdateAction::Updated);
+        }
+        match self.image.version.cmp(&current) {
+            std::cmp::Ordering::Equal => Ok(UpdateAction::SkippedSameVersion),
+            std::cmp::Ordering::Less if !self.allow_downgrade => Err(HailoError::InvalidOperation),
+            _ => Ok(UpdateAction::Updated),
+        }
+    }
+
+    /// Checks the image against `device`, updates it and optionally resets.
+    /// A downgrade without [`allow_downgrade`](Self::allow_downgrade) fails
+    /// with `InvalidOperation`; an image for another chip family fails with
+    /// `InvalidFirmwareMagic`.  Errors are only returned before the image is
+    /// sent; see [`FirmwareUpdateReport::confirmation`] for the rest.
+    pub fn run(self, device: &Device) -> HailoResult<FirmwareUpdateReport> {
+        self.run_with_progress(device, |_| {})
+    }
+
+    /// Like [`run`](Self::run), calling `progress` as each stage starts.
+    pub fn run_with_progress(
+        mut self,
+        device: &Device,
+        mut progress: impl FnMut(UpdateStage),
+    ) -> HailoResult<FirmwareUpdateReport> {
+        progress(UpdateStage::Identifying);
+        let identity = device.identify()?;
+        if FirmwareFamily::for_architecture(identity.device_architecture)
+            .is_some_and(|family| family != self.image.family)
+        {
+            return Err(HailoError::InvalidFirmwareMagic);
+        }
+        let action = self.plan(identity.fw_version)?;
+        let mut report = FirmwareUpdateReport {
+            kind: self.image.kind,
+            image_version: self.image.version,
+            previous_version: identity.fw_version,
+            action,
+            reset: None,
+            confirmation: None,
+        };
+        if action == UpdateAction::SkippedSameVersion {
+            return Ok(report);
+        }
+
+        let size =
+            u32::try_from(self.image.bytes.len()).map_err(|_| HailoError::InvalidArgument)?;
+        let buffer = self.image.bytes.as_mut_ptr().cast::<c_void>();
+        progress(UpdateStage::Flashing);
+        check(unsafe {
+            match self.image.kind {
+                ImageKind::Firmware => hailo_update_firmware(device.as_raw(), buffer, size),
+                ImageKind::SecondStage => hailo_update_second_stage(device.as_raw(), buffer, size),
+            }
+        })?;
+
+        if let Some(mode) = self.reset {
+            progress(UpdateStage::Resetting);
+            report.reset = Some(mode);
+            let confirmation = check(unsafe { hailo_reset_device(device.as_raw(), mode.into()) })
+                .and_then(|()| {
+                    progress(UpdateStage::Confirming);
+                    device.identify().map(|identity| identity.fw_version)
+                });
+            report.confirmation = Some(confirmation);
+        }
+        Ok(report)
+    }
+}
+
+impl Device {
+    /// Validates and installs the firmware image at `path` without resetting.
+    /// Use [`FirmwareUpdate`] for reset, downgrade and force options.
+    pub fn update_firmware(&self, path: impl AsRef<Path>) -> HailoResult<FirmwareUpdateReport> {
+        FirmwareUpdate::new(FirmwareImage::from_file(ImageKind::Firmware, path)?).run(self)
+    }
+
+    /// Validates and installs the second-stage image at `path`.
+    pub fn update_second_stage(&self, path: impl AsRef<Path>) -> HailoResult<FirmwareUpdateReport> {
+        FirmwareUpdate::new(FirmwareImage::from_file(ImageKind::SecondStage, path)?).run(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HAILO8_MAGIC: u32 = 0x1DD8_9DE0;
+
+    fn section(out: &mut Vec<u8>, magic: u32, version: [u32; 3], code_size: u32) {
+        for word in [magic, 0, version[0], version[1], version[2], code_size] {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+        out.resize(out.len() + code_size as usize, 0xAA);
+    }
+
+    fn certificate(out: &mut Vec<u8>, key_size: u32, content_size: u32) {
+        out.extend_from_slice(&key_size.to_le_bytes());
+        out.extend_from_slice(&content_size.to_le_bytes());
+        out.resize(out.len() + (key_size + content_size) as usize, 0xCC);
+    }
+
+    fn firmware(core_version: [u32; 3]) -> Vec<u8> {
+        let mut out = Vec::new();
+        section(&mut out, HAILO8_MAGIC, [4, 20, 0], 16);
+        certificate(&mut out, 8, 4);
+        section(&mut out, HAILO8_MAGIC, core_version, 8);
+        out
+    }
+
+    #[test]
+    fn parses_valid_firmware() {
+        let image = FirmwareImage::firmware(firmware([4, 20, 0])).unwrap();
+        assert_eq!(image.family, FirmwareFamily::Hailo8);
+        assert_eq!(image.version.to_string(), "4.20.0");
+        assert_eq!(image.sections.len(), 2);
+        assert_eq!(image.sections[1].cpu, CpuId::Cpu1);
+        assert_eq!(
+            (image.key_certificate_size, image.content_certificate_size),
+            (8, 4)
+        );
+    }
+
+    #[test]
+    fn header_errors_map_to_firmware_statuses() {
+        let mut bad_magic = firmware([4, 20, 0]);
+        bad_magic[0] ^= 0xFF;
+        assert_eq!(
+            FirmwareImage::firmware(bad_magic),
+            Err(HailoError::InvalidFirmwareMagic)
+        );
+
+        let mut too_big = Vec::new();
+        section(&mut too_big, HAILO8_MAGIC, [4, 20, 0], 0);
+        too_big[20..24].copy_from_slice(&(MAX_APP_CODE_SIZE + 1).to_le_bytes());
+        assert_eq!(
+            FirmwareImage::firmware(too_big),
+            Err(HailoError::InvalidFirmwareCodeSize)
+        );
+
+        let mut bad_key = Vec::new();
+        section(&mut bad_key, HAILO8_MAGIC, [4, 20, 0], 4);
+        bad_key.extend_from_slice(&(MAX_CERT_KEY_SIZE + 1).to_le_bytes());
+        bad_key.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(
+            FirmwareImage::firmware(bad_key),
+            Err(HailoError::InvalidKeyCertificateSize)
+        );
+
+        let mut app_only = Vec::new();
+        section(&mut app_only, HAILO8_MAGIC, [4, 20, 0], 4);
+        certificate(&mut app_only, 0, 0);
+        assert_eq!(
+            FirmwareImage::firmware(app_only),
+            Err(HailoError::InvalidFirmwareCpuId)
+        );
+
+        let mut trailing = firmware([4, 20, 0]);
+        trailing.push(0);
+        assert_eq!(
+            FirmwareImage::firmware(trailing),
+            Err(HailoError::MismatchingFirmwareBufferSizes)
+        );
+        assert_eq!(
+            FirmwareImage::firmware(firmware([4, 19, 0])),
+            Err(HailoError::InvalidFirmware)
+        );
+    }
+
+    #[test]
+    fn plan_compares_versions() {
+        let image = FirmwareImage::firmware(firmware([4, 20, 0])).unwrap();
+        let v = |major, minor| Version {
+            major,
+            minor,
+            revision: 0,
+        };
+        let update = FirmwareUpdate::new(image);
+        assert_eq!(update.plan(v(4, 19)), Ok(UpdateAction::Updated));
+        assert_eq!(update.plan(v(4, 20)), Ok(UpdateAction::SkippedSameVersion));
+        assert_eq!(update.plan(v(4, 21)), Err(HailoError::InvalidOperation));
+        let update = update.allow_downgrade(true).force(true);
+        assert_eq!(update.plan(v(4, 21)), Ok(UpdateAction::Updated));
+        assert_eq!(update.plan(v(4, 20)), Ok(UpdateAction::Updated));
+    }
+
+    #[test]
+    fn confirmation_failures_are_reported() {
+        let version = Version {
+            major: 4,
+            minor: 20,
+            revision: 0,
+        };
+        let mut report = FirmwareUpdateReport {
+            kind: ImageKind::Firmware,
+            image_version: version,
+            previous_version: Version {
+                minor: 19,
+                ..version
+            },
+            action: UpdateAction::Updated,
+            reset: Some(ResetDeviceMode::Soft),
+            confirmation: Some(Err(HailoError::Timeout)),
+        };
+        assert!(!report.is_confirmed());
+        report.confirmation = Some(Ok(report.previous_version));
+        assert!(!report.is_confirmed());
+        report.confirmation = Some(Ok(version));
+        assert!(report.is_confirmed());
+    }
+}
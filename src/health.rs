@@ -0,0 +1,338 @@
+//! Typed health-monitor states and device health snapshots.
+//!
+//! [`Device::health`] gathers temperature, health-monitor, performance and
+//! throttling information in one call.  Each part is optional because not
+//! every device or firmware implements every query.
+
+use std::mem::MaybeUninit;
+use std::os::raw::c_int;
+
+use crate::device::Device;
+use crate::enums::*;
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{
+    hailo_get_chip_temperature, hailo_get_health_information, hailo_get_health_stats,
+    hailo_get_performance_stats, hailo_get_throttling_state,
+};
+use crate::typed_enums::c_enum;
+use crate::types::{
+    hailo_chip_temperature_info_t, hailo_health_info_t, hailo_health_stats_t,
+    hailo_performance_stats_t, hailo_throttling_level_t,
+};
+
+c_enum! {
+    /// Temperature-protection zone
+    /// (`hailo_temperature_protection_temperature_zone_t`).
+    pub enum TemperatureZone: hailo_temperature_protection_temperature_zone_t {
+        Green = HAILO_TEMPERATURE_PROTECTION_TEMPERATURE_ZONE__GREEN => "green",
+        Orange = HAILO_TEMPERATURE_PROTECTION_TEMPERATURE_ZONE__ORANGE => "orange",
+        Red = HAILO_TEMPERATURE_PROTECTION_TEMPERATURE_ZONE__RED => "red",
+    }
+}
+
+c_enum! {
+    /// Overcurrent-protection zone
+    /// (`hailo_overcurrent_protection_overcurrent_zone_t`).
+    pub enum OvercurrentZone: hailo_overcurrent_protection_overcurrent_zone_t {
+        Green = HAILO_OVERCURRENT_PROTECTION_OVERCURRENT_ZONE__GREEN => "green",
+        Red = HAILO_OVERCURRENT_PROTECTION_OVERCURRENT_ZONE__RED => "red",
+    }
+}
+
+c_enum! {
+    /// Hardware throttling state (`hailo_hw_throttling_state_t`).
+    pub enum ThrottlingState: hailo_hw_throttling_state_t {
+        None = HAILO_THROTTLING_STATE_NONE => "none",
+        Light = HAILO_THROTTLING_STATE_0_LIGHT => "light",
+        Medium = HAILO_THROTTLING_STATE_1_MEDIUM => "medium",
+        Heavy = HAILO_THROTTLING_STATE_2_HEAVY => "heavy",
+        Severe = HAILO_THROTTLING_STATE_3_SEVERE => "severe",
+        StreamsOff = HAILO_THROTTLING_STATE_4_STREAMS_OFF => "streams_off",
+        Overheat = HAILO_THROTTLING_STATE_OVERHEAT => "overheat",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Snapshots
+// ---------------------------------------------------------------------------
+
+/// On-die sensor readings in °C (`hailo_chip_temperature_info_t`).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChipTemperature {
+    pub ts0: f32,
+    pub ts1: f32,
+    pub sample_count: u16,
+}
+
+impl From<&hailo_chip_temperature_info_t> for ChipTemperature {
+    fn from(raw: &hailo_chip_temperature_info_t) -> Self {
+        Self {
+            ts0: raw.ts0_temperature,
+            ts1: raw.ts1_temperature,
+            sample_count: raw.sample_count,
+        }
+    }
+}
+
+/// One entry of the temperature-throttling table.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ThrottlingLevel {
+    pub temperature_threshold: f32,
+    pub hysteresis_temperature_threshold: f32,
+    pub nn_clock_freq: u32,
+}
+
+impl From<&hailo_throttling_level_t> for ThrottlingLevel {
+    fn from(raw: &hailo_throttling_level_t) -> Self {
+        Self {
+            temperature_threshold: raw.temperature_threshold,
+            hysteresis_temperature_threshold: raw.hysteresis_temperature_threshold,
+            nn_clock_freq: raw.throttling_nn_clock_freq,
+        }
+    }
+}
+
+/// Health-monitor state (`hailo_health_info_t`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthInfo {
+    pub overcurrent_protection_active: bool,
+    pub overcurrent_zone: OvercurrentZone,
+    pub red_overcurrent_threshold: f32,
+    pub overcurrent_throttling_active: bool,
+    pub temperature_throttling_active: bool,
+    pub temperature_zone: TemperatureZone,
+    /// Index into `temperature_throttling_levels`; `None` when no level
+    /// applies (negative in C).
+    pub temperature_throttling_level: Option<usize>,
+    pub temperature_throttling_levels: Vec<ThrottlingLevel>,
+    pub orange_temperature_threshold: i32,
+    pub orange_hysteresis_temperature_threshold: i32,
+    pub red_temperature_threshold: i32,
+    pub red_hysteresis_temperature_threshold: i32,
+    pub requested_overcurrent_clock_freq: u32,
+    pub requested_temperature_clock_freq: u32,
+}
+
+impl HealthInfo {
+    /// The currently applied throttling level, if any.
+    pub fn current_throttling_level(&self) -> Option<&ThrottlingLevel> {
+        self.temperature_throttling_levels
+            .get(self.temperature_throttling_level?)
+    }
+}
+
+impl From<&hailo_health_info_t> for HealthInfo {
+    fn from(raw: &hailo_health_info_t) -> Self {
+        Self {
+            overcurrent_protection_active: raw.overcurrent_protection_active,
+            overcurrent_zone: c_int::from(raw.current_overcurrent_zone).into(),
+            red_overcurrent_threshold: raw.red_overcurrent_threshold,
+            overcurrent_throttling_active: raw.overcurrent_throttling_active,
+            temperature_throttling_active: raw.temperature_throttling_active,
+            temperature_zone: c_int::from(raw.current_temperature_zone).into(),
+            temperature_throttling_level: usize::try_from(raw.current_temperature_throttling_level)
+                .ok(),
+            temperature_throttling_levels: raw
+                .temperature_throttling_levels
+                .iter()
+                .map(ThrottlingLevel::from)
+                .collect(),
+            orange_temperature_threshold: raw.orange_temperature_threshold,
+            orange_hysteresis_temperature_threshold: raw.orange_hysteresis_temperature_threshold,
+            red_temperature_threshold: raw.red_temperature_threshold,
+            red_hysteresis_temperature_threshold: raw.red_hysteresis_temperature_threshold,
+            requested_overcurrent_clock_freq: raw.requested_overcurrent_clock_freq,
+            requested_temperature_clock_freq: raw.requested_temperature_clock_freq,
+        }
+    }
+}
+
+/// Device utilisation (`hailo_performance_stats_t`).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PerformanceStats {
+    /// Percent.
+    pub cpu_utilization: f32,
+    /// Bytes.
+    pub ram_size_total: i64,
+    /// Bytes.
+    pub ram_size_used: i64,
+    /// Percent.
+    pub nnc_utilization: f32,
+    pub ddr_noc_total_transactions: i32,
+    /// Percent.
+    pub dsp_utilization: i32,
+}
+
+impl From<&hailo_performance_stats_t> for PerformanceStats {
+    fn from(raw: &hailo_performance_stats_t) -> Self {
+        Self {
+            cpu_utilization: raw.cpu_utilization,
+            ram_size_total: raw.ram_size_total,
+            ram_size_used: raw.ram_size_used,
+            nnc_utilization: raw.nnc_utilization,
+            ddr_noc_total_transactions: raw.ddr_noc_total_transactions,
+            dsp_utilization: raw.dsp_utilization,
+        }
+    }
+}
+
+/// Die-level health statistics (`hailo_health_stats_t`).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HealthStats {
+    /// °C.
+    pub on_die_temperature: f32,
+    /// mV.
+    pub on_die_voltage: i32,
+    pub bist_failure_mask: i32,
+}
+
+impl From<&hailo_health_stats_t> for HealthStats {
+    fn from(raw: &hailo_health_stats_t) -> Self {
+        Self {
+            on_die_temperature: raw.on_die_temperature,
+            on_die_voltage: raw.on_die_voltage,
+            bist_failure_mask: raw.bist_failure_mask,
+        }
+    }
+}
+
+/// Everything [`Device::health`] could read.  A part is `None` when the
+/// device answered `HAILO_NOT_SUPPORTED`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthSnapshot {
+    pub temperature: Option<ChipTemperature>,
+    pub info: Option<HealthInfo>,
+    pub performance: Option<PerformanceStats>,
+    pub stats: Option<HealthStats>,
+    /// Whether firmware throttling is enabled.
+    pub throttling_active: Option<bool>,
+}
+
+/// Maps `NotSupported` to `Ok(None)`.
+fn supported<T>(result: HailoResult<T>) -> HailoResult<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(HailoError::NotSupported) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+impl Device {
+    pub fn chip_temperature(&self) -> HailoResult<ChipTemperature> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_get_chip_temperature(self.as_raw(), raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(ChipTemperature::from(&raw))
+    }
+
+    pub fn health_information(&self) -> HailoResult<HealthInfo> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_get_health_information(self.as_raw(), raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(HealthInfo::from(&raw))
+    }
+
+    pub fn performance_stats(&self) -> HailoResult<PerformanceStats> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_get_performance_stats(self.as_raw(), raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(PerformanceStats::from(&raw))
+    }
+
+    pub fn health_stats(&self) -> HailoResult<HealthStats> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_get_health_stats(self.as_raw(), raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(HealthStats::from(&raw))
+    }
+
+    /// Whether firmware throttling is enabled (`hailo_get_throttling_state`).
+    pub fn throttling_active(&self) -> HailoResult<bool> {
+        let mut active = false;
+        check(unsafe { hailo_get_throttling_state(self.as_raw(), &mut active) })?;
+        Ok(active)
+    }
+
+    /// Reads every health query.  Unsupported queries become `None`; any
+    /// other failure is returned.
+    pub fn health(&self) -> HailoResult<HealthSnapshot> {
+        Ok(HealthSnapshot {
+            temperature: supported(self.chip_temperature())?,
+            info: supported(self.health_information())?,
+            performance: supported(self.performance_stats())?,
+            stats: supported(self.health_stats())?,
+            throttling_active: supported(self.throttling_active())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zones_decode_and_preserve_unknown() {
+        assert_eq!(TemperatureZone::from(1), TemperatureZone::Orange);
+        assert_eq!(TemperatureZone::from(9), TemperatureZone::Unknown(9));
+        assert_eq!(OvercurrentZone::from(1), OvercurrentZone::Red);
+        assert_eq!(TemperatureZone::Red.to_string(), "red");
+    }
+
+    #[test]
+    fn throttling_states_decode() {
+        assert_eq!(ThrottlingState::from(0), ThrottlingState::None);
+        assert_eq!(ThrottlingState::from(5), ThrottlingState::StreamsOff);
+        assert_eq!(ThrottlingState::from(6), ThrottlingState::Overheat);
+        assert_eq!(ThrottlingState::from(7), ThrottlingState::Unknown(7));
+    }
+
+    #[test]
+    fn health_info_decodes_zones_and_level() {
+        let level = |threshold: f32, freq| hailo_throttling_level_t {
+            temperature_threshold: threshold,
+            hysteresis_temperature_threshold: threshold - 5.0,
+            throttling_nn_clock_freq: freq,
+        };
+        let mut raw = hailo_health_info_t {
+            overcurrent_protection_active: true,
+            current_overcurrent_zone: 1,
+            red_overcurrent_threshold: 8.0,
+            overcurrent_throttling_active: false,
+            temperature_throttling_active: true,
+            current_temperature_zone: 1,
+            current_temperature_throttling_level: 2,
+            temperature_throttling_levels: [
+                level(80.0, 400),
+                level(90.0, 300),
+                level(100.0, 200),
+                level(110.0, 100),
+            ],
+            orange_temperature_threshold: 95,
+            orange_hysteresis_temperature_threshold: 90,
+            red_temperature_threshold: 120,
+            red_hysteresis_temperature_threshold: 115,
+            requested_overcurrent_clock_freq: 0,
+            requested_temperature_clock_freq: 200,
+        };
+        let info = HealthInfo::from(&raw);
+        assert_eq!(info.overcurrent_zone, OvercurrentZone::Red);
+        assert_eq!(info.temperature_zone, TemperatureZone::Orange);
+        assert_eq!(info.temperature_throttling_levels.len(), 4);
+        assert_eq!(info.current_throttling_level().unwrap().nn_clock_freq, 200);
+
+        raw.current_temperature_throttling_level = -1;
+        let info = HealthInfo::from(&raw);
+        assert_eq!(info.temperature_throttling_level, None);
+        assert_eq!(info.current_throttling_level(), None);
+    }
+
+    #[test]
+    fn only_not_supported_is_optional() {
+        assert_eq!(supported(Ok(1)), Ok(Some(1)));
+        assert_eq!(supported::<()>(Err(HailoError::NotSupported)), Ok(None));
+        assert_eq!(
+            supported::<()>(Err(HailoError::Timeout)),
+            Err(HailoError::Timeout)
+        );
+    }
+}
@@ -0,0 +1,425 @@
+//! Safe wrapper around a loaded HEF and its topology queries.
+//!
+//! Info queries return owned structs.  The anonymous shape unions of
+//! `hailo_stream_info_t` / `hailo_vstream_info_t` are decoded into [`Shape`]
+//! using `format.order` as the discriminant.
+
+use std::ffi::c_void;
+use std::fmt;
+use std::path::Path;
+use std::ptr;
+
+use crate::constants::{
+    HAILO_MAX_NETWORK_GROUPS, HAILO_MAX_NETWORKS_IN_NETWORK_GROUP, HAILO_MAX_STREAMS_COUNT,
+};
+use crate::cstr::CFixedStr;
+use crate::enums::is_nms_order;
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{
+    hailo_create_hef_buffer, hailo_create_hef_file, hailo_hef_get_all_vstream_infos,
+    hailo_hef_get_network_group_infos, hailo_hef_get_network_infos, hailo_hef_get_stream_infos,
+    hailo_release_hef,
+};
+use crate::handles::hailo_hef;
+use crate::typed_enums::{Format, NmsBurstType, StreamDirection};
+use crate::types::{
+    hailo_3d_image_shape_t, hailo_network_group_info_t, hailo_network_info_t, hailo_nms_info_t,
+    hailo_nms_shape_t, hailo_quant_info_t, hailo_stream_info_t, hailo_vstream_info_t,
+};
+use crate::util::{c_string, fetch_array, path_c_string};
+
+// ---------------------------------------------------------------------------
+// Owned info structs
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ImageShape {
+    pub height: u32,
+    pub width: u32,
+    pub features: u32,
+}
+
+impl From<hailo_3d_image_shape_t> for ImageShape {
+    fn from(raw: hailo_3d_image_shape_t) -> Self {
+        Self {
+            height: raw.height,
+            width: raw.width,
+            features: raw.features,
+        }
+    }
+}
+
+/// Decoded shape union, selected by `format.order`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Shape {
+    Tensor {
+        height: u32,
+        width: u32,
+        features: u32,
+    },
+    Nms {
+        number_of_classes: u32,
+        max_bboxes_per_class: u32,
+        max_bboxes_total: u32,
+        /// Only reported for vstreams; zero for raw streams.
+        max_accumulated_mask_size: u32,
+    },
+}
+
+impl From<hailo_3d_image_shape_t> for Shape {
+    fn from(raw: hailo_3d_image_shape_t) -> Self {
+        Self::Tensor {
+            height: raw.height,
+            width: raw.width,
+            features: raw.features,
+        }
+    }
+}
+
+impl From<hailo_nms_shape_t> for Shape {
+    fn from(raw: hailo_nms_shape_t) -> Self {
+        Self::Nms {
+            number_of_classes: raw.number_of_classes,
+            max_bboxes_per_class: raw.max_bboxes_per_class,
+            max_bboxes_total: raw.max_bboxes_total,
+            max_accumulated_mask_size: raw.max_accumulated_mask_size,
+        }
+    }
+}
+
+/// Stream-level NMS layout (`hailo_nms_info_t`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NmsInfo {
+    pub number_of_classes: u32,
+    pub max_bboxes_per_class: u32,
+    pub max_bboxes_total: u32,
+    pub bbox_size: u32,
+    pub chunks_per_frame: u32,
+    pub burst_size: u32,
+    pub burst_type: NmsBurstType,
+    /// `(class_group_index, original_name)` when the stream is defused.
+    pub defuse: Option<(u32, String)>,
+}
+
+impl From<&hailo_nms_info_t> for NmsInfo {
+    fn from(raw: &hailo_nms_info_t) -> Self {
+        Self {
+            number_of_classes: raw.number_of_classes,
+            max_bboxes_per_class: raw.max_bboxes_per_class,
+            max_bboxes_total: raw.max_bboxes_total,
+            bbox_size: raw.bbox_size,
+            chunks_per_frame: raw.chunks_per_frame,
+            burst_size: raw.burst_size,
+            burst_type: raw.burst_type.into(),
+            defuse: raw.is_defused.then(|| {
+                (
+                    raw.defuse_info.class_group_index,
+                    raw.defuse_info.original_name.to_string_lossy().into_owned(),
+                )
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkGroupInfo {
+    pub name: String,
+    pub is_multi_context: bool,
+}
+
+impl From<&hailo_network_group_info_t> for NetworkGroupInfo {
+    fn from(raw: &hailo_network_group_info_t) -> Self {
+        Self {
+            name: raw.name.to_string_lossy().into_owned(),
+            is_multi_context: raw.is_multi_context,
+        }
+    }
+}
+
+/// Owned copy of `hailo_stream_info_t`.
+#[derive(Debug, Clone)]
+pub struct StreamInfo {
+    pub name: String,
+    pub direction: StreamDirection,
+    pub index: u8,
+    pub format: Format,
+    pub shape: Shape,
+    /// Device-side tensor shape; `None` for NMS streams.
+    pub hw_shape: Option<ImageShape>,
+    /// Full NMS layout; `None` for tensor streams.
+    pub nms_info: Option<NmsInfo>,
+    pub hw_data_bytes: u32,
+    pub hw_frame_size: u32,
+    pub quant_info: hailo_quant_info_t,
+    pub is_mux: bool,
+}
+
+impl From<&hailo_stream_info_t> for StreamInfo {
+    fn from(raw: &hailo_stream_info_t) -> Self {
+        let (shape, hw_shape, nms_info) = if is_nms_order(raw.format.order) {
+            // SAFETY: NMS orders select the `nms_info` branch.
+            let nms = unsafe { &raw.shape.nms_info };
+            let shape = Shape::Nms {
+                number_of_classes: nms.number_of_classes,
+                max_bboxes_per_class: nms.max_bboxes_per_class,
+                max_bboxes_total: nms.max_bboxes_total,
+                max_accumulated_mask_size: 0,
+            };
+            (shape, None, Some(NmsInfo::from(nms)))
+        } else {
+            // SAFETY: every other order selects the `shapes` branch.
+            let shapes = unsafe { raw.shape.shapes };
+            (shapes.shape.into(), Some(shapes.hw_shape.into()), None)
+        };
+        Self {
+            name: raw.name.to_string_lossy().into_owned(),
+            direction: raw.direction.into(),
+            index: raw.index,
+            format: raw.format.into(),
+            shape,
+            hw_shape,
+            nms_info,
+            hw_data_bytes: raw.hw_data_bytes,
+            hw_frame_size: raw.hw_frame_size,
+            quant_info: raw.quant_info,
+            is_mux: raw.is_mux,
+        }
+    }
+}
+
+/// Owned copy of `hailo_vstream_info_t`.
+#[derive(Debug, Clone)]
+pub struct VStreamInfo {
+    pub name: String,
+    pub network_name: String,
+    pub direction: StreamDirection,
+    pub format: Format,
+    pub shape: Shape,
+    pub quant_info: hailo_quant_info_t,
+}
+
+impl From<&hailo_vstream_info_t> for VStreamInfo {
+    fn from(raw: &hailo_vstream_info_t) -> Self {
+        let shape = if is_nms_order(raw.format.order) {
+            // SAFETY: NMS orders select the `nms_shape` branch.
+            Shape::from(unsafe { raw.shape.nms_shape })
+        } else {
+            // SAFETY: every other order selects the `shape` branch.
+            Shape::from(unsafe { raw.shape.shape })
+        };
+        Self {
+            name: raw.name.to_string_lossy().into_owned(),
+            network_name: raw.network_name.to_string_lossy().into_owned(),
+            direction: raw.direction.into(),
+            format: raw.format.into(),
+            shape,
+            quant_info: raw.quant_info,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Hef
+// ---------------------------------------------------------------------------
+
+/// Owned, parsed HEF.  Released on drop.  `Send` and `Sync`: it is
+/// read-only once parsed.
+pub struct Hef {
+    raw: hailo_hef,
+    /// Copy of the image passed to [`Hef::from_bytes`], kept for the lifetime
+    /// of the handle in case the runtime refers back to it.
+    _buffer: Option<Box<[u8]>>,
+}
+
+// SAFETY: a parsed HEF is immutable; every `hailo_hef` call only reads it.
+unsafe impl Send for Hef {}
+// SAFETY: as above.
+unsafe impl Sync for Hef {}
+
+impl Hef {
+    /// Parses a HEF file (`hailo_create_hef_file`).
+    pub fn from_file(path: impl AsRef<Path>) -> HailoResult<Self> {
+        let path = path_c_string(path.as_ref())?;
+        let mut raw = ptr::null_mut();
+        check(unsafe { hailo_create_hef_file(&mut raw, path.as_ptr()) })?;
+        Ok(Self { raw, _buffer: None })
+    }
+
+    /// Parses a HEF image from memory (`hailo_create_hef_buffer`).  The bytes
+    /// are copied, so `bytes` may be dropped as soon as this returns.
+    pub fn from_bytes(bytes: &[u8]) -> HailoResult<Self> {
+        if bytes.is_empty() {
+            return Err(HailoError::InvalidArgument);
+        }
+        let buffer: Box<[u8]> = bytes.into();
+        let mut raw = ptr::null_mut();
+        check(unsafe {
+            hailo_create_hef_buffer(&mut raw, buffer.as_ptr().cast::<c_void>(), buffer.len())
+        })?;
+        Ok(Self {
+            raw,
+            _buffer: Some(buffer),
+        })
+    }
+
+    pub fn as_raw(&self) -> hailo_hef {
+        self.raw
+    }
+
+    pub fn network_groups(&self) -> HailoResult<Vec<NetworkGroupInfo>> {
+        let infos = fetch_array::<hailo_network_group_info_t>(
+            HAILO_MAX_NETWORK_GROUPS,
+            |ptr, len| unsafe { hailo_hef_get_network_group_infos(self.raw, ptr, len) },
+        )?;
+        Ok(infos.iter().map(NetworkGroupInfo::from).collect())
+    }
+
+    /// Network names inside `group`.
+    pub fn networks(&self, group: &str) -> HailoResult<Vec<String>> {
+        let group = c_string(group)?;
+        let infos = fetch_array::<hailo_network_info_t>(
+            HAILO_MAX_NETWORKS_IN_NETWORK_GROUP,
+            |ptr, len| unsafe { hailo_hef_get_network_infos(self.raw, group.as_ptr(), ptr, len) },
+        )?;
+        Ok(infos
+            .iter()
+            .map(|info| info.name.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    pub fn stream_infos(&self, group: &str) -> HailoResult<Vec<StreamInfo>> {
+        let group = c_string(group)?;
+        let infos =
+            fetch_array::<hailo_stream_info_t>(HAILO_MAX_STREAMS_COUNT, |ptr, len| unsafe {
+                hailo_hef_get_stream_infos(self.raw, group.as_ptr(), ptr, len)
+            })?;
+        Ok(infos.iter().map(StreamInfo::from).collect())
+    }
+
+    pub fn vstream_infos(&self, group: &str) -> HailoResult<Vec<VStreamInfo>> {
+        let group = c_string(group)?;
+        let infos =
+            fetch_array::<hailo_vstream_info_t>(HAILO_MAX_STREAMS_COUNT, |ptr, len| unsafe {
+                hailo_hef_get_all_vstream_infos(self.raw, group.as_ptr(), ptr, len)
+            })?;
+        Ok(infos.iter().map(VStreamInfo::from).collect())
+    }
+}
+
+impl fmt::Debug for Hef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hef")
+            .field("raw", &self.raw)
+            .field("buffer_len", &self._buffer.as_ref().map(|b| b.len()))
+            .finish()
+    }
+}
+
+impl Drop for Hef {
+    fn drop(&mut self) {
+        unsafe { hailo_release_hef(self.raw) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{
+        HAILO_FORMAT_ORDER_HAILO_NMS, HAILO_FORMAT_ORDER_HAILO_NMS_BY_CLASS,
+        HAILO_FORMAT_ORDER_NHWC,
+    };
+    use std::mem::zeroed;
+    use std::os::raw::c_char;
+
+    fn set_name(dst: &mut [c_char], name: &str) {
+        for (d, s) in dst.iter_mut().zip(name.bytes()) {
+            *d = s as c_char;
+        }
+    }
+
+    #[test]
+    fn vstream_info_decodes_tensor_shape() {
+        let mut raw: hailo_vstream_info_t = unsafe { zeroed() };
+        set_name(&mut raw.name, "yolov8/input_layer1");
+        set_name(&mut raw.network_name, "yolov8");
+        raw.format.order = HAILO_FORMAT_ORDER_NHWC;
+        raw.shape.shape = hailo_3d_image_shape_t {
+            height: 640,
+            width: 640,
+            features: 3,
+        };
+        let info = VStreamInfo::from(&raw);
+        assert_eq!(info.name, "yolov8/input_layer1");
+        assert_eq!(info.network_name, "yolov8");
+        assert_eq!(
+            info.shape,
+            Shape::Tensor {
+                height: 640,
+                width: 640,
+                features: 3
+            }
+        );
+    }
+
+    #[test]
+    fn vstream_info_decodes_nms_shape() {
+        let mut raw: hailo_vstream_info_t = unsafe { zeroed() };
+        raw.format.order = HAILO_FORMAT_ORDER_HAILO_NMS_BY_CLASS;
+        raw.shape.nms_shape = hailo_nms_shape_t {
+            number_of_classes: 80,
+            max_bboxes_per_class: 100,
+            max_bboxes_total: 8000,
+            max_accumulated_mask_size: 0,
+        };
+        let Shape::Nms {
+            number_of_classes,
+            max_bboxes_total,
+            ..
+        } = VStreamInfo::from(&raw).shape
+        else {
+            panic!("expected NMS shape");
+        };
+        assert_eq!((number_of_classes, max_bboxes_total), (80, 8000));
+    }
+
+    #[test]
+    fn stream_info_splits_tensor_and_nms_details() {
+        let mut raw: hailo_stream_info_t = unsafe { zeroed() };
+        raw.format.order = HAILO_FORMAT_ORDER_NHWC;
+        raw.shape.shapes.shape = hailo_3d_image_shape_t {
+            height: 2,
+            width: 3,
+            features: 4,
+        };
+        raw.shape.shapes.hw_shape = hailo_3d_image_shape_t {
+            height: 2,
+            width: 3,
+            features: 8,
+        };
+        let info = StreamInfo::from(&raw);
+        assert_eq!(
+            info.hw_shape,
+            Some(ImageShape {
+                height: 2,
+                width: 3,
+                features: 8
+            })
+        );
+        assert!(info.nms_info.is_none());
+
+        let mut raw: hailo_stream_info_t = unsafe { zeroed() };
+        raw.format.order = HAILO_FORMAT_ORDER_HAILO_NMS;
+        raw.shape.nms_info.number_of_classes = 80;
+        raw.shape.nms_info.is_defused = true;
+        raw.shape.nms_info.defuse_info.class_group_index = 1;
+        set_name(
+            unsafe { &mut raw.shape.nms_info.defuse_info.original_name },
+            "nms1",
+        );
+        let info = StreamInfo::from(&raw);
+        assert!(info.hw_shape.is_none());
+        let nms = info.nms_info.unwrap();
+        assert_eq!(nms.number_of_classes, 80);
+        assert_eq!(nms.defuse, Some((1, "nms1".to_string())));
+    }
+}
@@ -0,0 +1,310 @@
+//! Register-level I²C access to peripherals behind the Hailo chip.
+//!
+//! [`HailoI2c`] binds a [`Device`] to an [`I2cSlaveConfig`] and exposes
+//! `read_register` / `write_register` over `hailo_i2c_read` /
+//! `hailo_i2c_write`.  With the `embedded-hal` feature it also implements
+//! `embedded_hal::i2c::I2c` for 7-bit addresses, so existing sensor drivers
+//! run unchanged.
+//!
+//! The firmware only performs register transactions: the first
+//! `register_width` bytes written select the register, the rest is data.
+//! Under `embedded-hal`, a transaction's adjacent writes are concatenated
+//! and a following run of reads is issued as one register read, so each
+//! write-then-read run must write exactly the register address.  Separate
+//! runs are separate bus transactions.
+
+use crate::device::Device;
+use crate::enums::{HAILO_BIG_ENDIAN, HAILO_LITTLE_ENDIAN, hailo_endianness_t};
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{hailo_i2c_read, hailo_i2c_write};
+use crate::typed_enums::c_enum;
+use crate::types::hailo_i2c_slave_config_t;
+
+c_enum! {
+    /// Byte order of register addresses on the bus (`hailo_endianness_t`).
+    pub enum Endianness: hailo_endianness_t {
+        Big = HAILO_BIG_ENDIAN => "big",
+        Little = HAILO_LITTLE_ENDIAN => "little",
+    }
+}
+
+/// Width of a slave's register address.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RegisterWidth {
+    Bits8,
+    Bits16,
+    Bits32,
+}
+
+impl RegisterWidth {
+    pub fn bytes(self) -> usize {
+        match self {
+            Self::Bits8 => 1,
+            Self::Bits16 => 2,
+            Self::Bits32 => 4,
+        }
+    }
+}
+
+/// Slave addressing (`hailo_i2c_slave_config_t`).  Defaults to 8-bit
+/// big-endian register addresses without holding the bus.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct I2cSlaveConfig {
+    pub bus_index: u8,
+    pub slave_address: u16,
+    pub register_width: RegisterWidth,
+    pub endianness: Endianness,
+    pub hold_bus: bool,
+}
+
+impl I2cSlaveConfig {
+    pub fn new(bus_index: u8, slave_address: u16) -> Self {
+        Self {
+            bus_index,
+            slave_address,
+            register_width: RegisterWidth::Bits8,
+            endianness: Endianness::Big,
+            hold_bus: false,
+        }
+    }
+
+    pub fn register_width(mut self, width: RegisterWidth) -> Self {
+        self.register_width = width;
+        self
+    }
+
+    pub fn endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    pub fn hold_bus(mut self, hold: bool) -> Self {
+        self.hold_bus = hold;
+        self
+    }
+
+    pub fn to_raw(&self) -> hailo_i2c_slave_config_t {
+        hailo_i2c_slave_config_t {
+            endianness: self.endianness.into(),
+            slave_address: self.slave_address,
+            register_address_size: self.register_width.bytes() as u8,
+            bus_index: self.bus_index,
+            should_hold_bus: self.hold_bus,
+        }
+    }
+
+    /// Decodes a register address as it appears on the bus.  `bytes` must
+    /// be exactly `register_width` long.
+    #[cfg_attr(not(feature = "embedded-hal"), allow(dead_code))]
+    fn decode_register(&self, bytes: &[u8]) -> HailoResult<u32> {
+        if bytes.len() != self.register_width.bytes() {
+            return Err(HailoError::InvalidArgument);
+        }
+        let fold = |acc: u32, &byte: &u8| (acc << 8) | u32::from(byte);
+        Ok(match self.endianness {
+            Endianness::Big => bytes.iter().fold(0, fold),
+            Endianness::Little => bytes.iter().rev().fold(0, fold),
+            Endianness::Unknown(_) => return Err(HailoError::InvalidArgument),
+        })
+    }
+
+    /// Splits a bus write into register address and payload.
+    #[cfg_attr(not(feature = "embedded-hal"), allow(dead_code))]
+    fn split_write<'b>(&self, bytes: &'b [u8]) -> HailoResult<(u32, &'b [u8])> {
+        let (register, data) = bytes
+            .split_at_checked(self.register_width.bytes())
+            .ok_or(HailoError::InvalidArgument)?;
+        Ok((self.decode_register(register)?, data))
+    }
+}
+
+/// I²C master on one of the chip's buses, bound to a slave config.
+#[derive(Debug)]
+pub struct HailoI2c<'d> {
+    device: &'d Device,
+    config: I2cSlaveConfig,
+}
+
+impl<'d> HailoI2c<'d> {
+    pub fn new(device: &'d Device, config: I2cSlaveConfig) -> Self {
+        Self { device, config }
+    }
+
+    pub fn config(&self) -> &I2cSlaveConfig {
+        &self.config
+    }
+
+    /// Reads `buf.len()` bytes starting at `register`.
+    pub fn read_register(&self, register: u32, buf: &mut [u8]) -> HailoResult<()> {
+        read(self.device, &self.config, register, buf)
+    }
+
+    /// Writes `data` starting at `register`.
+    pub fn write_register(&self, register: u32, data: &[u8]) -> HailoResult<()> {
+        write(self.device, &self.config, register, data)
+    }
+}
+
+fn read(
+    device: &Device,
+    config: &I2cSlaveConfig,
+    register: u32,
+    buf: &mut [u8],
+) -> HailoResult<()> {
+    let length = u32::try_from(buf.len()).map_err(|_| HailoError::InvalidArgument)?;
+    let raw = config.to_raw();
+    check(unsafe { hailo_i2c_read(device.as_raw(), &raw, register, buf.as_mut_ptr(), length) })
+}
+
+fn write(device: &Device, config: &I2cSlaveConfig, register: u32, data: &[u8]) -> HailoResult<()> {
+    let length = u32::try_from(data.len()).map_err(|_| HailoError::InvalidArgument)?;
+    let raw = config.to_raw();
+    check(unsafe { hailo_i2c_write(device.as_raw(), &raw, register, data.as_ptr(), length) })
+}
+
+#[cfg(feature = "embedded-hal")]
+mod hal {
+    use embedded_hal::i2c::{
+        ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation, SevenBitAddress,
+    };
+
+    use super::*;
+
+    /// The firmware reports a failed bus transaction, most often a slave
+    /// that did not acknowledge, as `HAILO_FW_CONTROL_FAILURE` without saying
+    /// which byte failed; a bus it could not acquire before the control
+    /// timed out (e.g. SCL held low) surfaces as `HAILO_TIMEOUT`.  HailoRT
+    /// has no status for a misplaced START/STOP, so `Bus` is never reported.
+    impl embedded_hal::i2c::Error for HailoError {
+        fn kind(&self) -> ErrorKind {
+            match self {
+                HailoError::FwControlFailure => {
+                    ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown)
+                }
+                HailoError::Timeout => ErrorKind::ArbitrationLoss,
+                _ => ErrorKind::Other,
+            }
+        }
+    }
+
+    impl ErrorType for HailoI2c<'_> {
+        type Error = HailoError;
+    }
+
+    impl HailoI2c<'_> {
+        fn transfer(&self, address: u16, operations: &mut [Operation<'_>]) -> HailoResult<()> {
+            let config = I2cSlaveConfig {
+                slave_address: address,
+                ..self.config
+            };
+            let mut rest = operations;
+            while !rest.is_empty() {
+                let mut written = Vec::new();
+                let mut split = 0;
+                while let Some(Operation::Write(bytes)) = rest.get(split) {
+                    written.extend_from_slice(bytes);
+                    split += 1;
+                }
+                let reads_end = split
+                    + rest[split..]
+                        .iter()
+                        .take_while(|op| matches!(op, Operation::Read(_)))
+                        .count();
+                let (run, tail) = rest.split_at_mut(reads_end);
+                rest = tail;
+                let reads = &mut run[split..];
+
+                if reads.is_empty() {
+                    let (register, data) = config.split_write(&written)?;
+                    write(self.device, &config, register, data)?;
+                    continue;
+                }
+                let register = config.decode_register(&written)?;
+                if let [Operation::Read(buf)] = reads {
+                    read(self.device, &config, register, buf)?;
+                    continue;
+                }
+                let total = reads.iter().map(op_len).sum();
+                let mut scratch = vec![0u8; total];
+                read(self.device, &config, register, &mut scratch)?;
+                let mut offset = 0;
+                for op in reads {
+                    if let Operation::Read(buf) = op {
+                        buf.copy_from_slice(&scratch[offset..offset + buf.len()]);
+                        offset += buf.len();
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn op_len(op: &Operation<'_>) -> usize {
+        match op {
+            Operation::Read(buf) => buf.len(),
+            Operation::Write(bytes) => bytes.len(),
+        }
+    }
+
+    impl I2c<SevenBitAddress> for HailoI2c<'_> {
+        fn transaction(
+            &mut self,
+            address: SevenBitAddress,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            self.transfer(u16::from(address), operations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_config_carries_width_and_endianness() {
+        let config = I2cSlaveConfig::new(1, 0x36)
+            .register_width(RegisterWidth::Bits16)
+            .endianness(Endianness::Little)
+            .hold_bus(true);
+        let raw = config.to_raw();
+        assert_eq!(raw.bus_index, 1);
+        assert_eq!(raw.slave_address, 0x36);
+        assert_eq!(raw.register_address_size, 2);
+        assert_eq!(raw.endianness, HAILO_LITTLE_ENDIAN);
+        assert!(raw.should_hold_bus);
+    }
+
+    #[test]
+    fn register_addresses_decode_by_width_and_order() {
+        let big = I2cSlaveConfig::new(0, 0x10).register_width(RegisterWidth::Bits16);
+        assert_eq!(big.decode_register(&[0x30, 0x1a]), Ok(0x301a));
+        let little = big.endianness(Endianness::Little);
+        assert_eq!(little.decode_register(&[0x30, 0x1a]), Ok(0x1a30));
+        assert_eq!(
+            big.decode_register(&[0x30]),
+            Err(HailoError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn writes_split_into_register_and_payload() {
+        let config = I2cSlaveConfig::new(0, 0x10);
+        assert_eq!(config.split_write(&[0x0f, 1, 2]), Ok((0x0f, &[1u8, 2][..])));
+        assert_eq!(config.split_write(&[0x0f]), Ok((0x0f, &[][..])));
+        assert_eq!(config.split_write(&[]), Err(HailoError::InvalidArgument));
+    }
+
+    #[test]
+    #[cfg(feature = "embedded-hal")]
+    fn bus_failures_map_to_hal_error_kinds() {
+        use embedded_hal::i2c::{Error, ErrorKind, NoAcknowledgeSource};
+
+        assert_eq!(
+            HailoError::FwControlFailure.kind(),
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown)
+        );
+        assert_eq!(HailoError::Timeout.kind(), ErrorKind::ArbitrationLoss);
+        assert_eq!(HailoError::InvalidArgument.kind(), ErrorKind::Other);
+    }
+}
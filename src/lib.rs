@@ -9,6 +9,62 @@
 //! invariants described in the upstream C API documentation:
 //! <https://hailo.ai/developer-zone/>
 //!
+//! # Thread safety
+//! The raw [`handles`] are plain pointers and neither `Send` nor `Sync`.  The
+//! safe wrappers implement both traits where HailoRT documents the
+//! underlying object as thread-safe:
+//!
+//! | Wrapper | `Send` | `Sync` |
+//! |---------|--------|--------|
+//! | [`Device`], [`DeviceRef`], [`VDevice`], [`Hef`] | yes | yes |
+//! | [`ConfiguredNetworkGroup`], [`ActivatedNetworkGroup`] | yes | yes |
+//! | [`NotificationSubscription`] | yes | yes |
+//! | [`InputStream`], [`OutputStream`] | yes | no |
+//! | [`InputVStream`], [`OutputVStream`] | yes | no |
+//!
+//! Wrappers that borrow a device, such as [`HailoI2c`] or [`Watchdog`],
+//! follow from `&Device` and are both.  A device can therefore be shared
+//! with a health-polling thread while each vstream is moved to its own
+//! reader or writer thread:
+//!
+//! ```
+//! # use hailort_sys::*;
+//! # fn run(device: &Device, outputs: Vec<OutputVStream<'_>>) -> HailoResult<()> {
+//! std::thread::scope(|s| {
+//!     s.spawn(|| device.health());
+//!     for output in outputs {
+//!         s.spawn(move || {
+//!             let mut frame = vec![0u8; output.frame_size()];
+//!             output.read_into(&mut frame)
+//!         });
+//!     }
+//! });
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Concurrent transfers on one stream are not supported, so sharing a
+//! stream between threads is a compile error:
+//!
+//! ```compile_fail,E0277
+//! # use hailort_sys::*;
+//! # fn run(output: &OutputVStream<'_>) {
+//! std::thread::scope(|s| {
+//!     s.spawn(|| output.read_into(&mut [0u8; 16]));
+//!     s.spawn(|| output.read_into(&mut [0u8; 16]));
+//! });
+//! # }
+//! ```
+//!
+//! ```compile_fail,E0277
+//! # use hailort_sys::*;
+//! # fn run(input: &InputStream<'_>) {
+//! std::thread::scope(|s| {
+//!     s.spawn(|| input.write(&[0; 16]));
+//! });
+//! # }
+//! ```
+//!
 //! # Linking
 //! The `build.rs` script attempts `pkg-config` first, then falls back to
 //! searching `/usr/lib` and `/usr/local/lib` for `libhailort.so`.
@@ -19,22 +75,83 @@
 //! | [`constants`] | Sizing limits and default parameter values |
 //! | [`handles`] | Opaque C handle types |
 //! | [`status`] | `hailo_status` return code and all error constants |
+//! | [`error`] | [`HailoError`] and the [`check`] helper |
+//! | [`cstr`] | [`CFixedStr`] accessors for `[c_char; N]` name fields |
+//! | [`device`] | Safe RAII [`Device`] wrapper with discovery and identification |
+//! | [`vdevice`] | [`VDevice`] and its parameter builder |
+//! | [`hef`] | [`Hef`] loading and typed topology introspection |
+//! | [`configure`] | [`ConfigureParams`] builder with by-name overrides |
+//! | [`network_group`] | Configured/activated network groups and raw streams |
+//! | [`vstream`] | Typed [`InputVStream`] / [`OutputVStream`] |
+//! | [`async_io`] | Future-based async transfers on raw streams |
+//! | [`buffer`] | Page-aligned [`FrameBuffer`] and recycling, optionally DMA-mapped [`BufferPool`] |
+//! | [`firmware`] | Header-validated firmware and second-stage updates |
+//! | [`health`] | Typed zones, throttling states and `Device::health` snapshots |
+//! | [`notification`] | Closure-based device notifications |
+//! | [`i2c`] | Register-level [`HailoI2c`] master (embedded-hal `I2c` with the `embedded-hal` feature) |
+//! | [`logging`] | Environment-variable control of libhailort's own log file and console output |
+//! | [`power`] | One-shot power readings and multi-channel measurement sessions |
+//! | [`sensor`] | Validated sensor/ISP config builder and stored-section access |
+//! | [`watchdog`] | Typed watchdog control and previous-run system state |
 //! | [`enums`] | All other C enum type aliases and their variants |
+//! | [`typed_enums`] | Rust enums and bit-flag sets parallel to the [`enums`] aliases |
 //! | [`types`] | Structs, unions, and callback type aliases |
 //! | [`ffi`] | `unsafe extern "C"` function declarations |
 
 #![allow(non_camel_case_types, non_snake_case, non_upper_case_globals)]
 
+pub mod async_io;
+pub mod buffer;
+pub mod configure;
 pub mod constants;
+pub mod cstr;
+pub mod device;
 pub mod enums;
+pub mod error;
 pub mod ffi;
+pub mod firmware;
 pub mod handles;
+pub mod health;
+pub mod hef;
+pub mod i2c;
+pub mod logging;
+pub mod network_group;
+pub mod notification;
+pub mod power;
+pub mod sensor;
 pub mod status;
+pub mod typed_enums;
 pub mod types;
+pub mod vdevice;
+pub mod vstream;
+pub mod watchdog;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod util;
 
+pub use async_io::*;
+pub use buffer::*;
+pub use configure::*;
 pub use constants::*;
+pub use cstr::*;
+pub use device::*;
 pub use enums::*;
+pub use error::*;
 pub use ffi::*;
+pub use firmware::*;
 pub use handles::*;
+pub use health::*;
+pub use hef::*;
+pub use i2c::*;
+pub use logging::*;
+pub use network_group::*;
+pub use notification::*;
+pub use power::*;
+pub use sensor::*;
 pub use status::*;
+pub use typed_enums::*;
 pub use types::*;
+pub use vdevice::*;
+pub use vstream::*;
+pub use watchdog::*;
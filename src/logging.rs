@@ -0,0 +1,124 @@
+//! Host-side libhailort logging.
+//!
+//! `hailort.h` has no logging calls.  libhailort configures its own logger
+//! from two environment variables, read once when the logger is created on
+//! the first HailoRT call of the process:
+//!
+//! - `HAILORT_LOGGER_PATH`: directory that receives `hailort.log`, or `NONE`
+//!   to write no log file ([`set_logger_path`]);
+//! - `HAILORT_CONSOLE_LOGGER_LEVEL`: lowest level printed to stderr
+//!   ([`set_console_logger_level`]).
+//!
+//! The helpers here only set those variables, so they must run before
+//! anything else touches HailoRT; later calls have no effect.  The library
+//! offers no callback or file-descriptor sink: to capture its console output,
+//! redirect the process's stderr.
+
+use std::env;
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::Path;
+
+use crate::error::{HailoError, HailoResult};
+
+/// Environment variable naming the `hailort.log` directory.
+pub const HAILORT_LOGGER_PATH: &str = "HAILORT_LOGGER_PATH";
+/// Environment variable holding the console [`LoggerLevel`].
+pub const HAILORT_CONSOLE_LOGGER_LEVEL: &str = "HAILORT_CONSOLE_LOGGER_LEVEL";
+
+/// `HAILORT_LOGGER_PATH` value that disables the log file.
+const NO_LOG_FILE: &str = "NONE";
+
+/// Console log level, spelled as libhailort expects it in
+/// `HAILORT_CONSOLE_LOGGER_LEVEL`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LoggerLevel {
+    Trace,
+    Debug,
+    Info,
+    Warning,
+    Error,
+    Critical,
+    /// Prints nothing to stderr.
+    Off,
+}
+
+impl LoggerLevel {
+    pub const ALL: &'static [Self] = &[
+        Self::Trace,
+        Self::Debug,
+        Self::Info,
+        Self::Warning,
+        Self::Error,
+        Self::Critical,
+        Self::Off,
+    ];
+
+    /// Value written to `HAILORT_CONSOLE_LOGGER_LEVEL`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Critical => "critical",
+            Self::Off => "off",
+        }
+    }
+}
+
+impl fmt::Display for LoggerLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Sets the directory `hailort.log` is written to, or with `None` disables
+/// the log file.  Fails with `InvalidArgument` if `dir` contains a NUL byte.
+///
+/// # Safety
+/// Modifies the process environment: no other thread may read or write it
+/// concurrently (see [`std::env::set_var`]).
+pub unsafe fn set_logger_path(dir: Option<&Path>) -> HailoResult<()> {
+    let value = dir.map_or(OsStr::new(NO_LOG_FILE), Path::as_os_str);
+    if value.as_encoded_bytes().contains(&0) {
+        return Err(HailoError::InvalidArgument);
+    }
+    // SAFETY: upheld by the caller.
+    unsafe { env::set_var(HAILORT_LOGGER_PATH, value) };
+    Ok(())
+}
+
+/// Sets the lowest level libhailort prints to stderr; [`LoggerLevel::Off`]
+/// silences it.
+///
+/// # Safety
+/// As for [`set_logger_path`].
+pub unsafe fn set_console_logger_level(level: LoggerLevel) {
+    // SAFETY: upheld by the caller.
+    unsafe { env::set_var(HAILORT_CONSOLE_LOGGER_LEVEL, level.as_str()) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_use_hailort_names() {
+        let names: Vec<_> = LoggerLevel::ALL.iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            names,
+            [
+                "trace", "debug", "info", "warning", "error", "critical", "off"
+            ]
+        );
+    }
+
+    #[test]
+    fn paths_with_nul_are_rejected() {
+        // SAFETY: rejected before the environment is touched.
+        let result = unsafe { set_logger_path(Some(Path::new("logs\0"))) };
+        assert_eq!(result, Err(HailoError::InvalidArgument));
+    }
+}
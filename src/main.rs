@@ -12,15 +12,14 @@ fn main() {
 
     let status = unsafe { hailort_sys::hailo_get_library_version(&mut version) };
 
-    if status == hailort_sys::HAILO_SUCCESS {
-        println!(
+    match hailort_sys::check(status) {
+        Ok(()) => println!(
             "HailoRT version: {}.{}.{}",
             version.major, version.minor, version.revision
-        );
-    } else {
-        let msg = unsafe { hailort_sys::hailo_get_status_message(status) };
-        let msg = unsafe { std::ffi::CStr::from_ptr(msg) };
-        eprintln!("hailo_get_library_version failed: {}", msg.to_string_lossy());
-        std::process::exit(1);
+        ),
+        Err(err) => {
+            eprintln!("hailo_get_library_version failed: {err}");
+            std::process::exit(1);
+        }
     }
 }
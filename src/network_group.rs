@@ -0,0 +1,319 @@
+//! Configured and activated network groups, and the raw streams they expose.
+//!
+//! The lifecycle is encoded in lifetimes:
+//!
+//! * a [`ConfiguredNetworkGroup`] borrows the [`Device`] or [`VDevice`] it was
+//!   configured on;
+//! * [`ConfiguredNetworkGroup::activate`] returns an [`ActivatedNetworkGroup`]
+//!   guard that deactivates the group on drop;
+//! * [`InputStream`] / [`OutputStream`] borrow that guard.
+//!
+//! Using a stream after deactivation, or after the device is released, is
+//! therefore a compile error rather than a crash:
+//!
+//! ```compile_fail,E0505
+//! # use hailort_sys::*;
+//! # fn run(group: &ConfiguredNetworkGroup<'_>) -> HailoResult<()> {
+//! let mut active = group.activate()?;
+//! let inputs = active.input_streams(None)?;
+//! drop(active); // deactivates the group...
+//! inputs[0].write(&[0; 16])?; // ...so the stream may no longer be used
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The stream getters borrow the guard mutably, so each stream has one
+//! handle at a time and cannot be fetched again while it is in use
+//! ([`ActivatedNetworkGroup::streams`] returns inputs and outputs together):
+//!
+//! ```compile_fail,E0499
+//! # use hailort_sys::*;
+//! # fn run(group: &ConfiguredNetworkGroup<'_>) -> HailoResult<()> {
+//! let mut active = group.activate()?;
+//! let inputs = active.input_streams(None)?;
+//! let again = active.input_streams(None)?; // the streams are already out
+//! inputs[0].write(&[0; 16])?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+
+use crate::async_io::PendingOps;
+use crate::configure::ConfigureParams;
+use crate::constants::{HAILO_MAX_NETWORK_GROUPS, HAILO_MAX_STREAMS_COUNT};
+use crate::device::Device;
+use crate::error::{HailoResult, check};
+use crate::ffi::{
+    hailo_activate_network_group, hailo_configure_device, hailo_configure_vdevice,
+    hailo_deactivate_network_group, hailo_get_input_streams_by_network,
+    hailo_get_network_group_info, hailo_get_output_streams_by_network, hailo_input_stream_write,
+    hailo_output_stream_get_info, hailo_output_stream_read, hailo_shutdown_network_group,
+    hailo_stream_get_info,
+};
+use crate::handles::{
+    hailo_activated_network_group, hailo_configured_network_group, hailo_input_stream,
+    hailo_output_stream,
+};
+use crate::hef::{Hef, NetworkGroupInfo, StreamInfo};
+use crate::types::hailo_configure_params_t;
+use crate::util::{c_string, fetch_array};
+use crate::vdevice::VDevice;
+
+fn params_ptr(params: Option<&mut ConfigureParams>) -> *mut hailo_configure_params_t {
+    params.map_or(ptr::null_mut(), ConfigureParams::as_mut_ptr)
+}
+
+impl Device {
+    /// Configures `hef` on this device (`hailo_configure_device`).  `None`
+    /// uses the runtime's default params.
+    pub fn configure(
+        &self,
+        hef: &Hef,
+        params: Option<&mut ConfigureParams>,
+    ) -> HailoResult<Vec<ConfiguredNetworkGroup<'_>>> {
+        let params = params_ptr(params);
+        let raw = fetch_array(HAILO_MAX_NETWORK_GROUPS, |ptr, len| unsafe {
+            hailo_configure_device(self.as_raw(), hef.as_raw(), params, ptr, len)
+        })?;
+        Ok(raw.into_iter().map(ConfiguredNetworkGroup::new).collect())
+    }
+}
+
+impl VDevice {
+    /// Configures `hef` on this vdevice (`hailo_configure_vdevice`).  `None`
+    /// uses the runtime's default params.
+    pub fn configure(
+        &self,
+        hef: &Hef,
+        params: Option<&mut ConfigureParams>,
+    ) -> HailoResult<Vec<ConfiguredNetworkGroup<'_>>> {
+        let params = params_ptr(params);
+        let raw = fetch_array(HAILO_MAX_NETWORK_GROUPS, |ptr, len| unsafe {
+            hailo_configure_vdevice(self.as_raw(), hef.as_raw(), params, ptr, len)
+        })?;
+        Ok(raw.into_iter().map(ConfiguredNetworkGroup::new).collect())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Configured
+// ---------------------------------------------------------------------------
+
+/// A network group loaded onto a device.  The handle is owned by the device,
+/// so it lives no longer than the `'d` borrow.
+///
+/// `Send` and `Sync`: activation and shutdown are serialised by the runtime,
+/// so [`shutdown`](Self::shutdown) may be called from a watchdog thread.
+#[derive(Debug)]
+pub struct ConfiguredNetworkGroup<'d> {
+    raw: hailo_configured_network_group,
+    _device: PhantomData<&'d ()>,
+}
+
+// SAFETY: HailoRT locks configured network groups internally.
+unsafe impl Send for ConfiguredNetworkGroup<'_> {}
+// SAFETY: as above.
+unsafe impl Sync for ConfiguredNetworkGroup<'_> {}
+
+impl ConfiguredNetworkGroup<'_> {
+    fn new(raw: hailo_configured_network_group) -> Self {
+        Self {
+            raw,
+            _device: PhantomData,
+        }
+    }
+
+    pub fn as_raw(&self) -> hailo_configured_network_group {
+        self.raw
+    }
+
+    pub fn info(&self) -> HailoResult<NetworkGroupInfo> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_get_network_group_info(self.raw, raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(NetworkGroupInfo::from(&raw))
+    }
+
+    /// Activates the group with default params.  It stays active until the
+    /// returned guard is dropped.
+    pub fn activate(&self) -> HailoResult<ActivatedNetworkGroup<'_>> {
+        let mut raw = ptr::null_mut();
+        check(unsafe { hailo_activate_network_group(self.raw, ptr::null_mut(), &mut raw) })?;
+        Ok(ActivatedNetworkGroup {
+            raw,
+            pending: Arc::default(),
+            _group: PhantomData,
+        })
+    }
+
+    /// Aborts in-flight transfers so blocked reads and writes return
+    /// (`hailo_shutdown_network_group`).
+    pub fn shutdown(&self) -> HailoResult<()> {
+        check(unsafe { hailo_shutdown_network_group(self.raw) })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Activated
+// ---------------------------------------------------------------------------
+
+/// Guard for an active network group.  Deactivates on drop, then waits for
+/// every async transfer issued on its streams to complete.
+///
+/// `Send` and `Sync`: deactivation may happen on any thread, and the stream
+/// getters take `&mut self`, so a shared guard cannot hand out a second
+/// handle to a stream another thread is using.
+#[derive(Debug)]
+pub struct ActivatedNetworkGroup<'a> {
+    raw: hailo_activated_network_group,
+    pending: Arc<PendingOps>,
+    _group: PhantomData<&'a ()>,
+}
+
+// SAFETY: stream lookup and deactivation are locked by the runtime.
+unsafe impl Send for ActivatedNetworkGroup<'_> {}
+// SAFETY: as above.
+unsafe impl Sync for ActivatedNetworkGroup<'_> {}
+
+impl ActivatedNetworkGroup<'_> {
+    pub fn as_raw(&self) -> hailo_activated_network_group {
+        self.raw
+    }
+
+    /// Input streams of `network`, or of every network when `None`.
+    pub fn input_streams(&mut self, network: Option<&str>) -> HailoResult<Vec<InputStream<'_>>> {
+        self.inputs(network)
+    }
+
+    /// Output streams of `network`, or of every network when `None`.
+    pub fn output_streams(&mut self, network: Option<&str>) -> HailoResult<Vec<OutputStream<'_>>> {
+        self.outputs(network)
+    }
+
+    /// Input and output streams of `network`, or of every network when
+    /// `None`, for callers that need both at once.
+    pub fn streams(
+        &mut self,
+        network: Option<&str>,
+    ) -> HailoResult<(Vec<InputStream<'_>>, Vec<OutputStream<'_>>)> {
+        Ok((self.inputs(network)?, self.outputs(network)?))
+    }
+
+    fn inputs(&self, network: Option<&str>) -> HailoResult<Vec<InputStream<'_>>> {
+        let network = network.map(c_string).transpose()?;
+        let name = network.as_ref().map_or(ptr::null(), |n| n.as_ptr());
+        let raw = fetch_array(HAILO_MAX_STREAMS_COUNT, |ptr, len| unsafe {
+            hailo_get_input_streams_by_network(self.raw, name, ptr, len)
+        })?;
+        Ok(raw
+            .into_iter()
+            .map(|raw| InputStream {
+                raw,
+                pending: Arc::clone(&self.pending),
+                _group: PhantomData,
+            })
+            .collect())
+    }
+
+    fn outputs(&self, network: Option<&str>) -> HailoResult<Vec<OutputStream<'_>>> {
+        let network = network.map(c_string).transpose()?;
+        let name = network.as_ref().map_or(ptr::null(), |n| n.as_ptr());
+        let raw = fetch_array(HAILO_MAX_STREAMS_COUNT, |ptr, len| unsafe {
+            hailo_get_output_streams_by_network(self.raw, name, ptr, len)
+        })?;
+        Ok(raw
+            .into_iter()
+            .map(|raw| OutputStream {
+                raw,
+                pending: Arc::clone(&self.pending),
+                _group: PhantomData,
+            })
+            .collect())
+    }
+}
+
+impl Drop for ActivatedNetworkGroup<'_> {
+    fn drop(&mut self) {
+        // Deactivation aborts in-flight async transfers; wait for their
+        // callbacks so none fires after the group is gone.
+        unsafe { hailo_deactivate_network_group(self.raw) };
+        self.pending.wait_idle();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Raw streams
+// ---------------------------------------------------------------------------
+
+/// Raw (non-transformed) input stream of an active network group.
+///
+/// `Send` but not `Sync`: it may be moved to a dedicated writer thread, but
+/// HailoRT does not support concurrent writes on one stream.
+#[derive(Debug)]
+pub struct InputStream<'a> {
+    raw: hailo_input_stream,
+    pub(crate) pending: Arc<PendingOps>,
+    _group: PhantomData<&'a ActivatedNetworkGroup<'a>>,
+}
+
+// SAFETY: stream handles are not tied to the creating thread.
+unsafe impl Send for InputStream<'_> {}
+
+impl InputStream<'_> {
+    pub fn as_raw(&self) -> hailo_input_stream {
+        self.raw
+    }
+
+    pub fn info(&self) -> HailoResult<StreamInfo> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_stream_get_info(self.raw, raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(StreamInfo::from(&raw))
+    }
+
+    /// Writes one frame in device (hw) layout.
+    pub fn write(&self, frame: &[u8]) -> HailoResult<()> {
+        check(unsafe {
+            hailo_input_stream_write(self.raw, frame.as_ptr().cast::<c_void>(), frame.len())
+        })
+    }
+}
+
+/// Raw (non-transformed) output stream of an active network group.
+///
+/// `Send` but not `Sync`: it may be moved to a dedicated reader thread, but
+/// HailoRT does not support concurrent reads on one stream.
+#[derive(Debug)]
+pub struct OutputStream<'a> {
+    raw: hailo_output_stream,
+    pub(crate) pending: Arc<PendingOps>,
+    _group: PhantomData<&'a ActivatedNetworkGroup<'a>>,
+}
+
+// SAFETY: stream handles are not tied to the creating thread.
+unsafe impl Send for OutputStream<'_> {}
+
+impl OutputStream<'_> {
+    pub fn as_raw(&self) -> hailo_output_stream {
+        self.raw
+    }
+
+    pub fn info(&self) -> HailoResult<StreamInfo> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_output_stream_get_info(self.raw, raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(StreamInfo::from(&raw))
+    }
+
+    /// Reads one frame in device (hw) layout.
+    pub fn read(&self, frame: &mut [u8]) -> HailoResult<()> {
+        check(unsafe {
+            hailo_output_stream_read(self.raw, frame.as_mut_ptr().cast::<c_void>(), frame.len())
+        })
+    }
+}
@@ -0,0 +1,424 @@
+//! Closure-based device notifications.
+//!
+//! [`Device::on_notification`] registers a Rust closure for one
+//! `HAILO_NOTIFICATION_ID_*`.  The payload union is decoded into a
+//! [`Notification`] before the closure runs.  The returned
+//! [`NotificationSubscription`] unregisters the callback and frees the
+//! closure on drop.
+//!
+//! The C API keeps one callback per device and id, so a second registration
+//! for the same pair fails with `InvalidOperation` until the first
+//! subscription is dropped; otherwise dropping the older one would remove
+//! the newer callback.
+
+use std::collections::HashSet;
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::{Mutex, PoisonError};
+
+use crate::device::Device;
+use crate::enums::*;
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{hailo_remove_notification_callback, hailo_set_notification_callback};
+use crate::handles::hailo_device;
+use crate::health::{OvercurrentZone, TemperatureZone, ThrottlingState};
+use crate::typed_enums::NotificationId;
+use crate::types::hailo_notification_t;
+
+/// A decoded device notification, one variant per `HAILO_NOTIFICATION_ID_*`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum Notification {
+    EthernetRxError {
+        error: u32,
+        queue_number: u32,
+        rx_errors_count: u32,
+    },
+    TemperatureAlarm {
+        zone: TemperatureZone,
+        alarm_ts_id: u32,
+        ts0_temperature: f32,
+        ts1_temperature: f32,
+    },
+    DataflowShutdown {
+        ts0_temperature: f32,
+        ts1_temperature: f32,
+    },
+    OvercurrentAlarm {
+        zone: OvercurrentZone,
+        exceeded_alert_threshold: f32,
+        is_last_overcurrent_violation_reached: bool,
+    },
+    LcuEccCorrectableError {
+        cluster_error: u16,
+    },
+    LcuEccUncorrectableError {
+        cluster_error: u16,
+    },
+    CpuEccError {
+        memory_bitmap: u32,
+    },
+    CpuEccFatal {
+        memory_bitmap: u32,
+    },
+    Debug {
+        connection_status: u32,
+        connection_type: u32,
+        vdma_is_active: u32,
+        host_port: u32,
+        host_ip_addr: u32,
+    },
+    ContextSwitchBreakpointReached {
+        network_group_index: u8,
+        batch_index: u32,
+        context_index: u16,
+        action_index: u16,
+    },
+    ClockChanged {
+        previous_clock: u32,
+        current_clock: u32,
+    },
+    InferDone {
+        infer_cycles: u32,
+    },
+    ContextSwitchRunTimeError {
+        exit_status: u32,
+        network_group_index: u8,
+        batch_index: u16,
+        context_index: u16,
+        action_index: u16,
+    },
+    NnCoreCrcError,
+    ThrottlingStateChange {
+        new_state: ThrottlingState,
+    },
+    /// An id this crate has no decoder for; the payload is not interpreted.
+    Unknown {
+        id: hailo_notification_id_t,
+    },
+}
+
+impl From<&hailo_notification_t> for Notification {
+    fn from(raw: &hailo_notification_t) -> Self {
+        // SAFETY: each arm reads the union member that `id` selects.
+        unsafe {
+            let body = &raw.body;
+            match raw.id {
+                HAILO_NOTIFICATION_ID_ETHERNET_RX_ERROR => {
+                    let m = body.rx_error_notification;
+                    Self::EthernetRxError {
+                        error: m.error,
+                        queue_number: m.queue_number,
+                        rx_errors_count: m.rx_errors_count,
+                    }
+                }
+                HAILO_NOTIFICATION_ID_HEALTH_MONITOR_TEMPERATURE_ALARM => {
+                    let m = body.health_monitor_temperature_alarm_notification;
+                    Self::TemperatureAlarm {
+                        zone: m.temperature_zone.into(),
+                        alarm_ts_id: m.alarm_ts_id,
+                        ts0_temperature: m.ts0_temperature,
+                        ts1_temperature: m.ts1_temperature,
+                    }
+                }
+                HAILO_NOTIFICATION_ID_HEALTH_MONITOR_DATAFLOW_SHUTDOWN => {
+                    let m = body.health_monitor_dataflow_shutdown_notification;
+                    Self::DataflowShutdown {
+                        ts0_temperature: m.ts0_temperature,
+                        ts1_temperature: m.ts1_temperature,
+                    }
+                }
+                HAILO_NOTIFICATION_ID_HEALTH_MONITOR_OVERCURRENT_ALARM => {
+                    let m = body.health_monitor_overcurrent_alert_notification;
+                    Self::OvercurrentAlarm {
+                        zone: m.overcurrent_zone.into(),
+                        exceeded_alert_threshold: m.exceeded_alert_threshold,
+                        is_last_overcurrent_violation_reached: m
+                            .is_last_overcurrent_violation_reached,
+                    }
+                }
+                HAILO_NOTIFICATION_ID_LCU_ECC_CORRECTABLE_ERROR => Self::LcuEccCorrectableError {
+                    cluster_error: body.health_monitor_lcu_ecc_error_notification.cluster_error,
+                },
+                HAILO_NOTIFICATION_ID_LCU_ECC_UNCORRECTABLE_ERROR => {
+                    Self::LcuEccUncorrectableError {
+                        cluster_error: body.health_monitor_lcu_ecc_error_notification.cluster_error,
+                    }
+                }
+                HAILO_NOTIFICATION_ID_CPU_ECC_ERROR => Self::CpuEccError {
+                    memory_bitmap: body.health_monitor_cpu_ecc_notification.memory_bitmap,
+                },
+                HAILO_NOTIFICATION_ID_CPU_ECC_FATAL => Self::CpuEccFatal {
+                    memory_bitmap: body.health_monitor_cpu_ecc_notification.memory_bitmap,
+                },
+                HAILO_NOTIFICATION_ID_DEBUG => {
+                    let m = body.debug_notification;
+                    Self::Debug {
+                        connection_status: m.connection_status,
+                        connection_type: m.connection_type,
+                        vdma_is_active: m.vdma_is_active,
+                        host_port: m.host_port,
+                        host_ip_addr: m.host_ip_addr,
+                    }
+                }
+                HAILO_NOTIFICATION_ID_CONTEXT_SWITCH_BREAKPOINT_REACHED => {
+                    let m = body.context_switch_breakpoint_reached_notification;
+                    Self::ContextSwitchBreakpointReached {
+                        network_group_index: m.network_group_index,
+                        batch_index: m.batch_index,
+                        context_index: m.context_index,
+                        action_index: m.action_index,
+                    }
+                }
+                HAILO_NOTIFICATION_ID_HEALTH_MONITOR_CLOCK_CHANGED_EVENT => {
+                    let m = body.health_monitor_clock_changed_notification;
+                    Self::ClockChanged {
+                        previous_clock: m.previous_clock,
+                        current_clock: m.current_clock,
+                    }
+                }
+                HAILO_NOTIFICATION_ID_HW_INFER_MANAGER_INFER_DONE => Self::InferDone {
+                    infer_cycles: body.hw_infer_manager_infer_done_notification.infer_cycles,
+                },
+                HAILO_NOTIFICATION_ID_CONTEXT_SWITCH_RUN_TIME_ERROR_EVENT => {
+                    let m = body.context_switch_run_time_error;
+                    Self::ContextSwitchRunTimeError {
+                        exit_status: m.exit_status,
+                        network_group_index: m.network_group_index,
+                        batch_index: m.batch_index,
+                        context_index: m.context_index,
+                        action_index: m.action_index,
+                    }
+                }
+                HAILO_NOTIFICATION_ID_NN_CORE_CRC_ERROR_EVENT => Self::NnCoreCrcError,
+                HAILO_NOTIFICATION_ID_THROTTLING_STATE_CHANGE_EVENT => {
+                    Self::ThrottlingStateChange {
+                        new_state: ThrottlingState::from(
+                            body.throttling_state_change.new_state as c_int,
+                        ),
+                    }
+                }
+                id => Self::Unknown { id },
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for hailo_notification_t {
+    /// Shows `body` decoded by `id` as a [`Notification`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("hailo_notification_t")
+            .field("id", &NotificationId::from(self.id))
+            .field("sequence", &self.sequence)
+            .field("body", &Notification::from(self))
+            .finish()
+    }
+}
+
+type Handler = Mutex<Box<dyn FnMut(Notification) + Send>>;
+
+/// `(device, id)` pairs with a live [`NotificationSubscription`].
+static REGISTERED: Mutex<Option<HashSet<(usize, hailo_notification_id_t)>>> = Mutex::new(None);
+
+/// Claims `(device, id)`, failing with `InvalidOperation` if it is taken.
+fn claim(device: hailo_device, id: hailo_notification_id_t) -> HailoResult<()> {
+    let mut registered = REGISTERED.lock().unwrap_or_else(PoisonError::into_inner);
+    if registered
+        .get_or_insert_default()
+        .insert((device as usize, id))
+    {
+        Ok(())
+    } else {
+        Err(HailoError::InvalidOperation)
+    }
+}
+
+fn release(device: hailo_device, id: hailo_notification_id_t) {
+    let mut registered = REGISTERED.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(registered) = registered.as_mut() {
+        registered.remove(&(device as usize, id));
+    }
+}
+
+unsafe extern "C" fn trampoline(
+    _device: hailo_device,
+    notification: *const hailo_notification_t,
+    opaque: *mut c_void,
+) {
+    let handler = unsafe { &*(opaque as *const Handler) };
+    let notification = Notification::from(unsafe { &*notification });
+    // A panicking handler must not unwind into the runtime's thread.
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        let mut handler = handler.lock().unwrap_or_else(PoisonError::into_inner);
+        handler(notification);
+    }));
+}
+
+/// Active notification callback.  Unregisters and frees the closure on drop.
+/// `Send` and `Sync`.
+#[must_use = "the callback is removed as soon as the subscription is dropped"]
+pub struct NotificationSubscription<'d> {
+    device: hailo_device,
+    id: NotificationId,
+    handler: *mut Handler,
+    _device: PhantomData<&'d Device>,
+}
+
+// SAFETY: the handler is `Send` and behind a mutex, and unregistering is
+// locked by the runtime, so the subscription may be dropped on any thread.
+unsafe impl Send for NotificationSubscription<'_> {}
+// SAFETY: shared access only reads `id`.
+unsafe impl Sync for NotificationSubscription<'_> {}
+
+impl NotificationSubscription<'_> {
+    pub fn id(&self) -> NotificationId {
+        self.id
+    }
+}
+
+impl std::fmt::Debug for NotificationSubscription<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotificationSubscription")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for NotificationSubscription<'_> {
+    fn drop(&mut self) {
+        // The runtime guarantees the callback is not running once removal
+        // returns, so the closure can be freed afterwards.
+        unsafe {
+            hailo_remove_notification_callback(self.device, self.id.into());
+            drop(Box::from_raw(self.handler));
+        }
+        release(self.device, self.id.into());
+    }
+}
+
+impl Device {
+    /// Calls `handler` on a runtime thread for every notification `id`.
+    ///
+    /// Fails with `InvalidOperation` while another subscription for `id`
+    /// on this device is alive.
+    pub fn on_notification(
+        &self,
+        id: NotificationId,
+        handler: impl FnMut(Notification) + Send + 'static,
+    ) -> HailoResult<NotificationSubscription<'_>> {
+        let raw_id = hailo_notification_id_t::from(id);
+        claim(self.as_raw(), raw_id)?;
+        let handler: *mut Handler = Box::into_raw(Box::new(Mutex::new(Box::new(handler))));
+        let status = unsafe {
+            hailo_set_notification_callback(self.as_raw(), trampoline, raw_id, handler.cast())
+        };
+        if let Err(err) = check(status) {
+            drop(unsafe { Box::from_raw(handler) });
+            release(self.as_raw(), raw_id);
+            return Err(err);
+        }
+        Ok(NotificationSubscription {
+            device: self.as_raw(),
+            id,
+            handler,
+            _device: PhantomData,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::zeroed;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn decodes_by_id() {
+        let mut raw: hailo_notification_t = unsafe { zeroed() };
+        raw.id = HAILO_NOTIFICATION_ID_HEALTH_MONITOR_TEMPERATURE_ALARM;
+        raw.body
+            .health_monitor_temperature_alarm_notification
+            .temperature_zone = HAILO_TEMPERATURE_PROTECTION_TEMPERATURE_ZONE__RED;
+        raw.body
+            .health_monitor_temperature_alarm_notification
+            .ts0_temperature = 101.5;
+        let Notification::TemperatureAlarm {
+            zone,
+            ts0_temperature,
+            ..
+        } = Notification::from(&raw)
+        else {
+            panic!("expected temperature alarm");
+        };
+        assert_eq!(zone, TemperatureZone::Red);
+        assert_eq!(ts0_temperature, 101.5);
+
+        raw.id = HAILO_NOTIFICATION_ID_THROTTLING_STATE_CHANGE_EVENT;
+        raw.body.throttling_state_change.new_state = 3;
+        assert_eq!(
+            Notification::from(&raw),
+            Notification::ThrottlingStateChange {
+                new_state: ThrottlingState::Heavy
+            }
+        );
+
+        raw.id = HAILO_NOTIFICATION_ID_COUNT;
+        assert_eq!(
+            Notification::from(&raw),
+            Notification::Unknown {
+                id: HAILO_NOTIFICATION_ID_COUNT
+            }
+        );
+    }
+
+    #[test]
+    fn trampoline_calls_closure_and_contains_panics() {
+        let seen = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&seen);
+        let handler: Handler = Mutex::new(Box::new(move |n| {
+            if let Notification::InferDone { infer_cycles } = n {
+                counter.store(infer_cycles, Ordering::SeqCst);
+            } else {
+                panic!("unexpected notification");
+            }
+        }));
+        let opaque = &handler as *const Handler as *mut c_void;
+
+        let mut raw: hailo_notification_t = unsafe { zeroed() };
+        raw.id = HAILO_NOTIFICATION_ID_HW_INFER_MANAGER_INFER_DONE;
+        raw.body
+            .hw_infer_manager_infer_done_notification
+            .infer_cycles = 42;
+        unsafe { trampoline(std::ptr::null_mut(), &raw, opaque) };
+        assert_eq!(seen.load(Ordering::SeqCst), 42);
+
+        raw.id = HAILO_NOTIFICATION_ID_NN_CORE_CRC_ERROR_EVENT;
+        unsafe { trampoline(std::ptr::null_mut(), &raw, opaque) };
+    }
+
+    #[test]
+    fn one_registration_per_device_and_id() {
+        let device = 0x1000 as hailo_device;
+        let other = 0x2000 as hailo_device;
+        let id = HAILO_NOTIFICATION_ID_HEALTH_MONITOR_TEMPERATURE_ALARM;
+        claim(device, id).unwrap();
+        assert_eq!(claim(device, id), Err(HailoError::InvalidOperation));
+        claim(other, id).unwrap();
+        claim(device, HAILO_NOTIFICATION_ID_DEBUG).unwrap();
+
+        release(device, id);
+        claim(device, id).unwrap();
+        for (device, id) in [
+            (device, id),
+            (other, id),
+            (device, HAILO_NOTIFICATION_ID_DEBUG),
+        ] {
+            release(device, id);
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! Power measurement: one-shot readings and continuous sampling sessions.
+//!
+//! The firmware samples up to four (DVM, measurement type) channels into
+//! `HAILO_MEASUREMENT_BUFFER_INDEX_0..3`.  [`PowerMeasurementSession`] wires
+//! the `set` → `start` → `get` → `stop` sequence and stops sampling on drop.
+
+use std::fmt;
+
+use crate::device::Device;
+use crate::enums::*;
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{
+    hailo_get_power_measurement, hailo_power_measurement, hailo_set_power_measurement,
+    hailo_start_power_measurement, hailo_stop_power_measurement,
+};
+use crate::typed_enums::{AveragingFactor, DvmOption, PowerMeasurementType, SamplingPeriod};
+use crate::types::hailo_power_measurement_data_t;
+
+/// Number of firmware measurement buffers.
+pub const MAX_POWER_CHANNELS: usize = 4;
+
+const BUFFER_INDICES: [hailo_measurement_buffer_index_t; MAX_POWER_CHANNELS] = [
+    HAILO_MEASUREMENT_BUFFER_INDEX_0,
+    HAILO_MEASUREMENT_BUFFER_INDEX_1,
+    HAILO_MEASUREMENT_BUFFER_INDEX_2,
+    HAILO_MEASUREMENT_BUFFER_INDEX_3,
+];
+
+/// Unit the firmware reports a measurement type in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PowerUnit {
+    Millivolts,
+    Milliamps,
+    Watts,
+}
+
+impl PowerUnit {
+    /// Shunt and bus voltages are in mV, current in mA and power in W.
+    /// `AUTO` resolves to power.
+    pub fn of(measurement_type: PowerMeasurementType) -> Self {
+        match measurement_type {
+            PowerMeasurementType::ShuntVoltage | PowerMeasurementType::BusVoltage => {
+                Self::Millivolts
+            }
+            PowerMeasurementType::Current => Self::Milliamps,
+            _ => Self::Watts,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Millivolts => "mV",
+            Self::Milliamps => "mA",
+            Self::Watts => "W",
+        }
+    }
+}
+
+impl fmt::Display for PowerUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// A single value from [`Device::measure`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PowerReading {
+    pub value: f32,
+    pub unit: PowerUnit,
+}
+
+impl fmt::Display for PowerReading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.unit)
+    }
+}
+
+/// Accumulated statistics of one session channel.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PowerStats {
+    pub dvm: DvmOption,
+    pub measurement_type: PowerMeasurementType,
+    pub unit: PowerUnit,
+    pub average: f32,
+    pub min: f32,
+    pub max: f32,
+    pub average_time_ms: f32,
+    pub samples: u32,
+}
+
+impl PowerStats {
+    fn new(channel: Channel, raw: hailo_power_measurement_data_t) -> Self {
+        Self {
+            dvm: channel.dvm,
+            measurement_type: channel.measurement_type,
+            unit: PowerUnit::of(channel.measurement_type),
+            average: raw.average_value,
+            min: raw.min_value,
+            max: raw.max_value,
+            average_time_ms: raw.average_time_value_milliseconds,
+            samples: raw.total_number_of_samples,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Channel {
+    dvm: DvmOption,
+    measurement_type: PowerMeasurementType,
+}
+
+impl Device {
+    /// Takes one reading (`hailo_power_measurement`).
+    pub fn measure(
+        &self,
+        dvm: DvmOption,
+        measurement_type: PowerMeasurementType,
+    ) -> HailoResult<PowerReading> {
+        let mut value = 0.0;
+        check(unsafe {
+            hailo_power_measurement(
+                self.as_raw(),
+                dvm.into(),
+                measurement_type.into(),
+                &mut value,
+            )
+        })?;
+        Ok(PowerReading {
+            value,
+            unit: PowerUnit::of(measurement_type),
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Session
+// ---------------------------------------------------------------------------
+
+/// Builder for [`PowerMeasurementSession`].  Channels are assigned to buffer
+/// indices in the order they are added.
+#[derive(Debug, Clone)]
+pub struct PowerMeasurementBuilder {
+    channels: Vec<Channel>,
+    averaging_factor: AveragingFactor,
+    sampling_period: SamplingPeriod,
+}
+
+impl Default for PowerMeasurementBuilder {
+    fn default() -> Self {
+        Self {
+            channels: Vec::new(),
+            averaging_factor: AveragingFactor::X256,
+            sampling_period: SamplingPeriod::Us1100,
+        }
+    }
+}
+
+impl PowerMeasurementBuilder {
+    /// Adds a channel; at most [`MAX_POWER_CHANNELS`] are accepted by `start`.
+    pub fn channel(mut self, dvm: DvmOption, measurement_type: PowerMeasurementType) -> Self {
+        self.channels.push(Channel {
+            dvm,
+            measurement_type,
+        });
+        self
+    }
+
+    /// Defaults to [`AveragingFactor::X256`].
+    pub fn averaging_factor(mut self, factor: AveragingFactor) -> Self {
+        self.averaging_factor = factor;
+        self
+    }
+
+    /// Defaults to [`SamplingPeriod::Us1100`].
+    pub fn sampling_period(mut self, period: SamplingPeriod) -> Self {
+        self.sampling_period = period;
+        self
+    }
+
+    fn validate(&self) -> HailoResult<()> {
+        if self.channels.is_empty() || self.channels.len() > MAX_POWER_CHANNELS {
+            return Err(HailoError::InvalidArgument);
+        }
+        Ok(())
+    }
+
+    /// Configures every channel, then starts sampling.
+    pub fn start(self, device: &Device) -> HailoResult<PowerMeasurementSession<'_>> {
+        self.validate()?;
+        for (&index, channel) in BUFFER_INDICES.iter().zip(&self.channels) {
+            check(unsafe {
+                hailo_set_power_measurement(
+                    device.as_raw(),
+                    index,
+                    channel.dvm.into(),
+                    channel.measurement_type.into(),
+                )
+            })?;
+        }
+        check(unsafe {
+            hailo_start_power_measurement(
+                device.as_raw(),
+                self.averaging_factor.into(),
+                self.sampling_period.into(),
+            )
+        })?;
+        Ok(PowerMeasurementSession {
+            device,
+            channels: self.channels,
+        })
+    }
+}
+
+/// Running power measurement.  Stops sampling on drop.
+#[derive(Debug)]
+pub struct PowerMeasurementSession<'d> {
+    device: &'d Device,
+    channels: Vec<Channel>,
+}
+
+impl PowerMeasurementSession<'_> {
+    pub fn builder() -> PowerMeasurementBuilder {
+        PowerMeasurementBuilder::default()
+    }
+
+    /// Number of configured channels.
+    pub fn channel_count(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Statistics for channel `index`, optionally resetting its buffer.
+    pub fn channel_stats(&self, index: usize, clear: bool) -> HailoResult<PowerStats> {
+        let channel = *self
+            .channels
+            .get(index)
+            .ok_or(HailoError::InvalidArgument)?;
+        let mut raw = hailo_power_measurement_data_t {
+            average_value: 0.0,
+            average_time_value_milliseconds: 0.0,
+            min_value: 0.0,
+            max_value: 0.0,
+            total_number_of_samples: 0,
+        };
+        check(unsafe {
+            hailo_get_power_measurement(
+                self.device.as_raw(),
+                BUFFER_INDICES[index],
+                clear,
+                &mut raw,
+            )
+        })?;
+        Ok(PowerStats::new(channel, raw))
+    }
+
+    /// Statistics for every channel, in the order they were added.
+    pub fn stats(&self, clear: bool) -> HailoResult<Vec<PowerStats>> {
+        (0..self.channels.len())
+            .map(|index| self.channel_stats(index, clear))
+            .collect()
+    }
+}
+
+impl Drop for PowerMeasurementSession<'_> {
+    fn drop(&mut self) {
+        unsafe { hailo_stop_power_measurement(self.device.as_raw()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units_follow_measurement_type() {
+        assert_eq!(
+            PowerUnit::of(PowerMeasurementType::ShuntVoltage),
+            PowerUnit::Millivolts
+        );
+        assert_eq!(
+            PowerUnit::of(PowerMeasurementType::BusVoltage),
+            PowerUnit::Millivolts
+        );
+        assert_eq!(
+            PowerUnit::of(PowerMeasurementType::Current),
+            PowerUnit::Milliamps
+        );
+        assert_eq!(PowerUnit::of(PowerMeasurementType::Power), PowerUnit::Watts);
+        assert_eq!(PowerUnit::of(PowerMeasurementType::Auto), PowerUnit::Watts);
+        let reading = PowerReading {
+            value: 1.5,
+            unit: PowerUnit::Watts,
+        };
+        assert_eq!(reading.to_string(), "1.5 W");
+    }
+
+    #[test]
+    fn channel_count_is_validated() {
+        let empty = PowerMeasurementSession::builder();
+        assert_eq!(empty.validate(), Err(HailoError::InvalidArgument));
+
+        let mut builder = PowerMeasurementSession::builder();
+        for _ in 0..MAX_POWER_CHANNELS {
+            builder = builder.channel(DvmOption::Auto, PowerMeasurementType::Power);
+        }
+        assert!(builder.validate().is_ok());
+        let builder = builder.channel(DvmOption::VddCore, PowerMeasurementType::Current);
+        assert_eq!(builder.validate(), Err(HailoError::InvalidArgument));
+    }
+
+    #[test]
+    fn stats_carry_channel_and_unit() {
+        let channel = Channel {
+            dvm: DvmOption::VddCore,
+            measurement_type: PowerMeasurementType::Current,
+        };
+        let raw = hailo_power_measurement_data_t {
+            average_value: 2.0,
+            average_time_value_milliseconds: 1.1,
+            min_value: 1.0,
+            max_value: 3.0,
+            total_number_of_samples: 10,
+        };
+        let stats = PowerStats::new(channel, raw);
+        assert_eq!(stats.unit, PowerUnit::Milliamps);
+        assert_eq!(
+            (stats.min, stats.average, stats.max, stats.samples),
+            (1.0, 2.0, 3.0, 10)
+        );
+    }
+}
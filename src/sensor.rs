@@ -0,0 +1,421 @@
+//! Sensor and ISP configuration storage.
+//!
+//! [`SensorConfig`] replaces the positional arguments of
+//! `hailo_store_sensor_config` / `hailo_store_isp_config` with a builder
+//! that validates the section index, config name and input files before
+//! calling C.  [`Device::sensor_section`] gives access to one stored
+//! section: reset, load-and-start, and [`SensorSection::dump_to`], which
+//! dumps the section to a file and parses it back.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::device::Device;
+use crate::enums::*;
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{
+    hailo_dump_sensor_config, hailo_load_and_start_sensor, hailo_reset_sensor,
+    hailo_set_sensor_i2c_bus_index, hailo_store_isp_config, hailo_store_sensor_config,
+};
+use crate::typed_enums::c_enum;
+use crate::util::c_string;
+
+/// Highest section index available for sensor configs; the section after it
+/// holds the ISP config.
+pub const MAX_SENSOR_SECTION_INDEX: u8 = 6;
+
+/// Longest accepted config name, in bytes (excluding the NUL).
+pub const MAX_SENSOR_CONFIG_NAME_LENGTH: usize = 23;
+
+c_enum! {
+    /// Sensor model (`hailo_sensor_types_t`).
+    pub enum SensorType: hailo_sensor_types_t {
+        Generic = HAILO_SENSOR_TYPES_GENERIC => "generic",
+        OnsemiAr0220at = HAILO_SENSOR_TYPES_ONSEMI_AR0220AT => "onsemi_ar0220at",
+        Raspicam = HAILO_SENSOR_TYPES_RASPICAM => "raspicam",
+        OnsemiAs0149at = HAILO_SENSOR_TYPES_ONSEMI_AS0149AT => "onsemi_as0149at",
+        Hailo8Isp = HAILO_SENSOR_TYPES_HAILO8_ISP => "hailo8_isp",
+    }
+}
+
+fn check_section_index(index: u8) -> HailoResult<()> {
+    if index > MAX_SENSOR_SECTION_INDEX {
+        return Err(HailoError::InvalidArgument);
+    }
+    Ok(())
+}
+
+fn check_file(path: &Path) -> HailoResult<()> {
+    if !path.is_file() {
+        return Err(HailoError::OpenFileFailure);
+    }
+    Ok(())
+}
+
+fn path_string(path: &Path) -> HailoResult<std::ffi::CString> {
+    c_string(path.to_str().ok_or(HailoError::InvalidArgument)?)
+}
+
+// ---------------------------------------------------------------------------
+// Store
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ConfigFiles {
+    Sensor {
+        section_index: u8,
+        sensor_type: SensorType,
+        config: PathBuf,
+    },
+    Isp {
+        static_config: PathBuf,
+        runtime_config: PathBuf,
+    },
+}
+
+/// A validated sensor or ISP config, ready to [`store`](Self::store).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SensorConfig {
+    files: ConfigFiles,
+    reset_config_size: u32,
+    width: u16,
+    height: u16,
+    fps: u16,
+    name: String,
+}
+
+/// Builder for [`SensorConfig`].  Resolution, FPS and reset-config size
+/// default to 0.
+#[derive(Debug, Clone)]
+pub struct SensorConfigBuilder {
+    config: SensorConfig,
+}
+
+impl SensorConfig {
+    /// A sensor config read from the CSV file `config`, stored in section 0
+    /// unless [`section_index`](SensorConfigBuilder::section_index) says
+    /// otherwise.
+    pub fn builder(sensor_type: SensorType, config: impl Into<PathBuf>) -> SensorConfigBuilder {
+        SensorConfigBuilder::new(ConfigFiles::Sensor {
+            section_index: 0,
+            sensor_type,
+            config: config.into(),
+        })
+    }
+
+    /// An ISP config built from its static and runtime files.
+    pub fn isp_builder(
+        static_config: impl Into<PathBuf>,
+        runtime_config: impl Into<PathBuf>,
+    ) -> SensorConfigBuilder {
+        SensorConfigBuilder::new(ConfigFiles::Isp {
+            static_config: static_config.into(),
+            runtime_config: runtime_config.into(),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Writes the config to the device's flash.
+    pub fn store(&self, device: &Device) -> HailoResult<()> {
+        let name = c_string(&self.name)?;
+        match &self.files {
+            ConfigFiles::Sensor {
+                section_index,
+                sensor_type,
+                config,
+            } => {
+                let config = path_string(config)?;
+                check(unsafe {
+                    hailo_store_sensor_config(
+                        device.as_raw(),
+                        u32::from(*section_index),
+                        (*sensor_type).into(),
+                        self.reset_config_size,
+                        self.height,
+                        self.width,
+                        self.fps,
+                        config.as_ptr(),
+                        name.as_ptr(),
+                    )
+                })
+            }
+            ConfigFiles::Isp {
+                static_config,
+                runtime_config,
+            } => {
+                let static_config = path_string(static_config)?;
+                let runtime_config = path_string(runtime_config)?;
+                check(unsafe {
+                    hailo_store_isp_config(
+                        device.as_raw(),
+                        self.reset_config_size,
+                        self.height,
+                        self.width,
+                        self.fps,
+                        static_config.as_ptr(),
+                        runtime_config.as_ptr(),
+                        name.as_ptr(),
+                    )
+                })
+            }
+        }
+    }
+}
+
+impl SensorConfigBuilder {
+    fn new(files: ConfigFiles) -> Self {
+        Self {
+            config: SensorConfig {
+                files,
+                reset_config_size: 0,
+                width: 0,
+                height: 0,
+                fps: 0,
+                name: String::new(),
+            },
+        }
+    }
+
+    /// Flash section for a sensor config; ignored for ISP configs.
+    pub fn section_index(mut self, index: u8) -> Self {
+        if let ConfigFiles::Sensor { section_index, .. } = &mut self.config.files {
+            *section_index = index;
+        }
+        self
+    }
+
+    pub fn resolution(mut self, width: u16, height: u16) -> Self {
+        self.config.width = width;
+        self.config.height = height;
+        self
+    }
+
+    pub fn fps(mut self, fps: u16) -> Self {
+        self.config.fps = fps;
+        self
+    }
+
+    /// Size in bytes of the leading entries replayed by
+    /// [`SensorSection::reset`].
+    pub fn reset_config_size(mut self, size: u32) -> Self {
+        self.config.reset_config_size = size;
+        self
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.config.name = name.to_owned();
+        self
+    }
+
+    fn validate(&self) -> HailoResult<()> {
+        let config = &self.config;
+        if config.name.len() > MAX_SENSOR_CONFIG_NAME_LENGTH || config.name.contains('\0') {
+            return Err(HailoError::InvalidArgument);
+        }
+        match &config.files {
+            ConfigFiles::Sensor {
+                section_index,
+                config,
+                ..
+            } => {
+                check_section_index(*section_index)?;
+                check_file(config)
+            }
+            ConfigFiles::Isp {
+                static_config,
+                runtime_config,
+            } => {
+                check_file(static_config)?;
+                check_file(runtime_config)
+            }
+        }
+    }
+
+    /// Fails with `InvalidArgument` for an out-of-range section or a bad
+    /// name, and with `OpenFileFailure` if an input file does not exist.
+    pub fn build(self) -> HailoResult<SensorConfig> {
+        self.validate()?;
+        Ok(self.config)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Stored sections
+// ---------------------------------------------------------------------------
+
+/// One register write of a sensor config, as dumped by
+/// `hailo_dump_sensor_config` (`operation,length,page,address,bitmask,value`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SensorConfigEntry {
+    pub operation: u8,
+    pub length: u8,
+    pub page: u8,
+    pub address: u32,
+    pub bitmask: u32,
+    pub value: u32,
+}
+
+impl fmt::Display for SensorConfigEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{:#x},{:#x},{:#x}",
+            self.operation, self.length, self.page, self.address, self.bitmask, self.value
+        )
+    }
+}
+
+fn parse_number<T: TryFrom<u64>>(field: &str) -> Option<T> {
+    let field = field.trim();
+    let value = match field
+        .strip_prefix("0x")
+        .or_else(|| field.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+        None => field.parse().ok()?,
+    };
+    T::try_from(value).ok()
+}
+
+/// Parses a dumped section.  Blank lines, `#` comments and a non-numeric
+/// header line are skipped.
+fn parse_dump(text: &str) -> HailoResult<Vec<SensorConfigEntry>> {
+    let mut entries = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').collect();
+        let entry = match fields[..] {
+            [operation, length, page, address, bitmask, value] => (|| {
+                Some(SensorConfigEntry {
+                    operation: parse_number(operation)?,
+                    length: parse_number(length)?,
+                    page: parse_number(page)?,
+                    address: parse_number(address)?,
+                    bitmask: parse_number(bitmask)?,
+                    value: parse_number(value)?,
+                })
+            })(),
+            _ => None,
+        };
+        match entry {
+            Some(entry) => entries.push(entry),
+            None if entries.is_empty() && !line.starts_with(|c: char| c.is_ascii_digit()) => {}
+            None => return Err(HailoError::FileOperationFailure),
+        }
+    }
+    Ok(entries)
+}
+
+/// A stored sensor config section of one device.
+#[derive(Debug, Copy, Clone)]
+pub struct SensorSection<'d> {
+    device: &'d Device,
+    index: u8,
+}
+
+impl SensorSection<'_> {
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Replays the section's reset config.
+    pub fn reset(&self) -> HailoResult<()> {
+        check(unsafe { hailo_reset_sensor(self.device.as_raw(), self.index) })
+    }
+
+    pub fn load_and_start(&self) -> HailoResult<()> {
+        check(unsafe { hailo_load_and_start_sensor(self.device.as_raw(), self.index) })
+    }
+
+    /// Dumps the section to `path` and returns its parsed entries.
+    pub fn dump_to(&self, path: impl AsRef<Path>) -> HailoResult<Vec<SensorConfigEntry>> {
+        let path = path.as_ref();
+        let c_path = path_string(path)?;
+        check(unsafe {
+            hailo_dump_sensor_config(self.device.as_raw(), self.index, c_path.as_ptr())
+        })?;
+        let text = std::fs::read_to_string(path).map_err(|_| HailoError::FileOperationFailure)?;
+        parse_dump(&text)
+    }
+}
+
+impl Device {
+    /// Section `index` (0..=[`MAX_SENSOR_SECTION_INDEX`]) of the sensor
+    /// config flash.
+    pub fn sensor_section(&self, index: u8) -> HailoResult<SensorSection<'_>> {
+        check_section_index(index)?;
+        Ok(SensorSection {
+            device: self,
+            index,
+        })
+    }
+
+    /// Routes `sensor_type` to I²C bus `bus_index`.
+    pub fn set_sensor_i2c_bus_index(
+        &self,
+        sensor_type: SensorType,
+        bus_index: u8,
+    ) -> HailoResult<()> {
+        check(unsafe {
+            hailo_set_sensor_i2c_bus_index(self.as_raw(), sensor_type.into(), bus_index)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn existing_file() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml")
+    }
+
+    #[test]
+    fn builder_validates_before_calling_c() {
+        let file = existing_file();
+        let config = SensorConfig::builder(SensorType::Raspicam, &file)
+            .section_index(MAX_SENSOR_SECTION_INDEX)
+            .resolution(1920, 1080)
+            .fps(30)
+            .name("imx219")
+            .build()
+            .unwrap();
+        assert_eq!((config.width, config.height, config.fps), (1920, 1080, 30));
+
+        let bad_section = SensorConfig::builder(SensorType::Raspicam, &file)
+            .section_index(MAX_SENSOR_SECTION_INDEX + 1)
+            .build();
+        assert_eq!(bad_section, Err(HailoError::InvalidArgument));
+
+        let long_name = SensorConfig::builder(SensorType::Generic, &file)
+            .name(&"x".repeat(MAX_SENSOR_CONFIG_NAME_LENGTH + 1))
+            .build();
+        assert_eq!(long_name, Err(HailoError::InvalidArgument));
+
+        let missing = SensorConfig::isp_builder(&file, "/nonexistent/runtime.bin").build();
+        assert_eq!(missing, Err(HailoError::OpenFileFailure));
+    }
+
+    #[test]
+    fn parses_dumped_sections() {
+        let text = "operation,length,page,address,bitmask,value\n\
+                    0,2,0,0x301a,0xffff,0x10d8\n\
+                    \n\
+                    1,1,0,12,255,7\n";
+        let entries = parse_dump(text).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].address, 0x301a);
+        assert_eq!(entries[0].value, 0x10d8);
+        assert_eq!(entries[1].to_string(), "1,1,0,0xc,0xff,0x7");
+
+        assert_eq!(
+            parse_dump("0,2,0,0x301a,0xffff\n"),
+            Err(HailoError::FileOperationFailure)
+        );
+    }
+}
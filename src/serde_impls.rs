@@ -0,0 +1,829 @@
+//! `Serialize` / `Deserialize` for the plain structs in [`types`](crate::types)
+//! (`serde` feature).
+//!
+//! Field names follow the C structs.  Three kinds of field are translated:
+//!
+//! - `[c_char; N]` names become strings.  A name may fill all `N` bytes;
+//!   deserializing a longer one (or one holding a NUL) fails.
+//! - `c_int` / `u8` / `u16` enum fields become the symbolic names of the
+//!   matching [`typed_enums`](crate::typed_enums) type (`"nhwc"`,
+//!   `"unknown (42)"`), and flag fields `a | b` strings.
+//! - Unions become a single-key map naming the member their discriminating
+//!   field selects, e.g. `{"nms_info": {...}}` in `hailo_stream_info_t`.
+//!
+//! Structs holding pointers (`hailo_vdevice_params_t`, pixel buffers, async
+//! completion infos, …), the flexible-array `hailo_detections_t` and the
+//! fixed tables of `hailo_configure_params_t` are not covered.
+//! Neither is `hailo_notification_t`: decode it with
+//! [`Notification::from`](crate::Notification) and serialize that instead.
+
+use std::os::raw::{c_char, c_int};
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::cstr::CFixedStr;
+use crate::enums::*;
+use crate::typed_enums::*;
+use crate::types::*;
+use crate::{Endianness, OvercurrentZone, TemperatureZone, ThrottlingState};
+
+/// Conversion between a raw field and its serialized representation.
+trait Field<Raw>: Sized {
+    fn encode(raw: &Raw) -> Self;
+    fn decode(self) -> Result<Raw, String>;
+}
+
+impl<T: Copy> Field<T> for T {
+    fn encode(raw: &T) -> Self {
+        *raw
+    }
+
+    fn decode(self) -> Result<T, String> {
+        Ok(self)
+    }
+}
+
+impl<const N: usize> Field<[c_char; N]> for String {
+    fn encode(raw: &[c_char; N]) -> Self {
+        raw.to_string_lossy().into_owned()
+    }
+
+    fn decode(self) -> Result<[c_char; N], String> {
+        // Unlike `CFixedStr::set`, a name may fill the whole field: the C
+        // side pairs several of these with a length (`serial_number`, …).
+        if self.len() > N || self.bytes().any(|b| b == 0) {
+            return Err(format!("name `{self}` does not fit in {N} bytes"));
+        }
+        let mut raw = [0; N];
+        for (dst, src) in raw.iter_mut().zip(self.bytes()) {
+            *dst = src as c_char;
+        }
+        Ok(raw)
+    }
+}
+
+macro_rules! symbolic_fields {
+    ($($ty:ty: $($raw:ty),*;)*) => {
+        $($(
+            impl Field<$raw> for $ty {
+                fn encode(raw: &$raw) -> Self {
+                    Self::from(c_int::from(*raw))
+                }
+
+                fn decode(self) -> Result<$raw, String> {
+                    <$raw>::try_from(c_int::from(self))
+                        .map_err(|_| format!("`{self}` is out of range"))
+                }
+            }
+        )*)*
+    };
+}
+
+symbolic_fields! {
+    DeviceArchitecture: c_int;
+    DeviceBootSource: c_int;
+    FormatType: c_int;
+    FormatOrder: c_int;
+    FormatFlags: c_int;
+    StreamTransformMode: c_int;
+    StreamDirection: c_int;
+    StreamInterface: c_int;
+    StreamFlags: c_int;
+    VStreamStatsFlags: c_int;
+    PipelineElemStatsFlags: c_int;
+    BufferFlags: c_int;
+    NmsBurstType: c_int;
+    Endianness: c_int;
+    TemperatureZone: c_int, u8;
+    OvercurrentZone: c_int, u8;
+    ThrottlingState: u16;
+}
+
+/// Implements both traits through a mirror struct whose fields hold the
+/// serialized representation.
+macro_rules! serde_structs {
+    ($($raw:ident { $($field:ident: $repr:ty,)* })*) => {
+        $(
+            impl Serialize for $raw {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    #[derive(Serialize)]
+                    struct Repr {
+                        $($field: $repr,)*
+                    }
+
+                    Repr {
+                        $($field: <$repr as Field<_>>::encode(&self.$field),)*
+                    }
+                    .serialize(serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $raw {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    #[derive(Deserialize)]
+                    struct Repr {
+                        $($field: $repr,)*
+                    }
+
+                    let repr = Repr::deserialize(deserializer)?;
+                    Ok(Self {
+                        $($field: repr.$field.decode().map_err(D::Error::custom)?,)*
+                    })
+                }
+            }
+        )*
+    };
+}
+
+serde_structs! {
+    // --- Version and identity ------------------------------------------------
+    hailo_version_t {
+        major: u32,
+        minor: u32,
+        revision: u32,
+    }
+    hailo_firmware_version_t {
+        major: u32,
+        minor: u32,
+        revision: u32,
+    }
+    hailo_pcie_device_info_t {
+        domain: u32,
+        bus: u32,
+        device: u32,
+        func: u32,
+    }
+    hailo_device_id_t {
+        id: String,
+    }
+    hailo_device_identity_t {
+        protocol_version: u32,
+        fw_version: hailo_firmware_version_t,
+        logger_version: u32,
+        board_name_length: u8,
+        board_name: String,
+        is_release: bool,
+        extended_context_switch_buffer: bool,
+        extended_fw_check: bool,
+        device_architecture: DeviceArchitecture,
+        serial_number_length: u8,
+        serial_number: String,
+        part_number_length: u8,
+        part_number: String,
+        product_name_length: u8,
+        product_name: String,
+    }
+    hailo_core_information_t {
+        is_release: bool,
+        extended_context_switch_buffer: bool,
+        extended_fw_check: bool,
+        fw_version: hailo_firmware_version_t,
+    }
+    hailo_device_supported_features_t {
+        ethernet: bool,
+        mipi: bool,
+        pcie: bool,
+        current_monitoring: bool,
+        mdio: bool,
+        power_measurement: bool,
+    }
+    hailo_extended_device_information_t {
+        neural_network_core_clock_rate: u32,
+        supported_features: hailo_device_supported_features_t,
+        boot_source: DeviceBootSource,
+        soc_id: [u8; crate::constants::HAILO_SOC_ID_LENGTH],
+        lcs: u8,
+        eth_mac_address: [u8; crate::constants::HAILO_ETH_MAC_LENGTH],
+        unit_level_tracking_id: [u8; crate::constants::HAILO_UNIT_LEVEL_TRACKING_BYTES_LENGTH],
+        soc_pm_values: [u8; crate::constants::HAILO_SOC_PM_VALUES_BYTES_LENGTH],
+        gpio_mask: u16,
+    }
+    hailo_fw_user_config_information_t {
+        version: u32,
+        entry_count: u32,
+        total_size: u32,
+    }
+
+    // --- Data format and quantisation ----------------------------------------
+    hailo_format_t {
+        type_: FormatType,
+        order: FormatOrder,
+        flags: FormatFlags,
+    }
+    hailo_quant_info_t {
+        qp_zp: f32,
+        qp_scale: f32,
+        limvals_min: f32,
+        limvals_max: f32,
+    }
+
+    // --- Stream and vstream parameters ---------------------------------------
+    hailo_transform_params_t {
+        transform_mode: StreamTransformMode,
+        user_buffer_format: hailo_format_t,
+    }
+    hailo_demux_params_t {
+        _reserved: u8,
+    }
+    hailo_pcie_input_stream_params_t {
+        _reserved: u8,
+    }
+    hailo_pcie_output_stream_params_t {
+        _reserved: u8,
+    }
+    hailo_integrated_input_stream_params_t {
+        _reserved: u8,
+    }
+    hailo_integrated_output_stream_params_t {
+        _reserved: u8,
+    }
+    hailo_stream_parameters_by_name_t {
+        name: String,
+        stream_params: hailo_stream_parameters_t,
+    }
+    hailo_vstream_params_t {
+        user_buffer_format: hailo_format_t,
+        timeout_ms: u32,
+        queue_size: u32,
+        vstream_stats_flags: VStreamStatsFlags,
+        pipeline_elements_stats_flags: PipelineElemStatsFlags,
+    }
+    hailo_input_vstream_params_by_name_t {
+        name: String,
+        params: hailo_vstream_params_t,
+    }
+    hailo_output_vstream_params_by_name_t {
+        name: String,
+        params: hailo_vstream_params_t,
+    }
+    hailo_output_vstream_name_by_group_t {
+        name: String,
+        pipeline_group_index: u8,
+    }
+
+    // --- Shapes, buffers and NMS ---------------------------------------------
+    hailo_3d_image_shape_t {
+        height: u32,
+        width: u32,
+        features: u32,
+    }
+    hailo_dma_buffer_t {
+        fd: c_int,
+        size: usize,
+    }
+    hailo_buffer_parameters_t {
+        flags: BufferFlags,
+    }
+    hailo_nms_defuse_info_t {
+        class_group_index: u32,
+        original_name: String,
+    }
+    hailo_nms_info_t {
+        number_of_classes: u32,
+        max_bboxes_per_class: u32,
+        max_bboxes_total: u32,
+        bbox_size: u32,
+        chunks_per_frame: u32,
+        burst_size: u32,
+        is_defused: bool,
+        defuse_info: hailo_nms_defuse_info_t,
+        burst_type: NmsBurstType,
+    }
+    hailo_nms_shape_t {
+        number_of_classes: u32,
+        max_bboxes_per_class: u32,
+        max_bboxes_total: u32,
+        max_accumulated_mask_size: u32,
+    }
+    hailo_bbox_t {
+        y_min: u16,
+        x_min: u16,
+        y_max: u16,
+        x_max: u16,
+        score: u16,
+    }
+    hailo_bbox_float32_t {
+        y_min: f32,
+        x_min: f32,
+        y_max: f32,
+        x_max: f32,
+        score: f32,
+    }
+    hailo_rectangle_t {
+        y_min: f32,
+        x_min: f32,
+        y_max: f32,
+        x_max: f32,
+    }
+    hailo_detection_t {
+        y_min: f32,
+        x_min: f32,
+        y_max: f32,
+        x_max: f32,
+        score: f32,
+        class_id: u16,
+    }
+    hailo_stream_info_shape_pair_t {
+        shape: hailo_3d_image_shape_t,
+        hw_shape: hailo_3d_image_shape_t,
+    }
+
+    // --- Notification messages -----------------------------------------------
+    hailo_rx_error_notification_message_t {
+        error: u32,
+        queue_number: u32,
+        rx_errors_count: u32,
+    }
+    hailo_debug_notification_message_t {
+        connection_status: u32,
+        connection_type: u32,
+        vdma_is_active: u32,
+        host_port: u32,
+        host_ip_addr: u32,
+    }
+    hailo_health_monitor_dataflow_shutdown_notification_message_t {
+        ts0_temperature: f32,
+        ts1_temperature: f32,
+    }
+    hailo_health_monitor_temperature_alarm_notification_message_t {
+        temperature_zone: TemperatureZone,
+        alarm_ts_id: u32,
+        ts0_temperature: f32,
+        ts1_temperature: f32,
+    }
+    hailo_health_monitor_overcurrent_alert_notification_message_t {
+        overcurrent_zone: OvercurrentZone,
+        exceeded_alert_threshold: f32,
+        is_last_overcurrent_violation_reached: bool,
+    }
+    hailo_health_monitor_lcu_ecc_error_notification_message_t {
+        cluster_error: u16,
+    }
+    hailo_health_monitor_cpu_ecc_notification_message_t {
+        memory_bitmap: u32,
+    }
+    hailo_context_switch_breakpoint_reached_message_t {
+        network_group_index: u8,
+        batch_index: u32,
+        context_index: u16,
+        action_index: u16,
+    }
+    hailo_health_monitor_clock_changed_notification_message_t {
+        previous_clock: u32,
+        current_clock: u32,
+    }
+    hailo_hw_infer_manager_infer_done_notification_message_t {
+        infer_cycles: u32,
+    }
+    hailo_start_update_cache_offset_notification_message_t {
+        cache_id_bitmask: u64,
+    }
+    hailo_context_switch_run_time_error_message_t {
+        exit_status: u32,
+        network_group_index: u8,
+        batch_index: u16,
+        context_index: u16,
+        action_index: u16,
+    }
+    hailo_throttling_state_change_message_t {
+        new_state: ThrottlingState,
+    }
+
+    // --- Power, temperature and health ---------------------------------------
+    hailo_power_measurement_data_t {
+        average_value: f32,
+        average_time_value_milliseconds: f32,
+        min_value: f32,
+        max_value: f32,
+        total_number_of_samples: u32,
+    }
+    hailo_chip_temperature_info_t {
+        ts0_temperature: f32,
+        ts1_temperature: f32,
+        sample_count: u16,
+    }
+    hailo_throttling_level_t {
+        temperature_threshold: f32,
+        hysteresis_temperature_threshold: f32,
+        throttling_nn_clock_freq: u32,
+    }
+    hailo_health_info_t {
+        overcurrent_protection_active: bool,
+        current_overcurrent_zone: OvercurrentZone,
+        red_overcurrent_threshold: f32,
+        overcurrent_throttling_active: bool,
+        temperature_throttling_active: bool,
+        current_temperature_zone: TemperatureZone,
+        current_temperature_throttling_level: i8,
+        temperature_throttling_levels:
+            [hailo_throttling_level_t; crate::constants::HAILO_MAX_TEMPERATURE_THROTTLING_LEVELS_NUMBER],
+        orange_temperature_threshold: i32,
+        orange_hysteresis_temperature_threshold: i32,
+        red_temperature_threshold: i32,
+        red_hysteresis_temperature_threshold: i32,
+        requested_overcurrent_clock_freq: u32,
+        requested_temperature_clock_freq: u32,
+    }
+    hailo_performance_stats_t {
+        cpu_utilization: f32,
+        ram_size_total: i64,
+        ram_size_used: i64,
+        nnc_utilization: f32,
+        ddr_noc_total_transactions: i32,
+        dsp_utilization: i32,
+    }
+    hailo_health_stats_t {
+        on_die_temperature: f32,
+        on_die_voltage: i32,
+        bist_failure_mask: i32,
+    }
+
+    // --- Networks, layers and streams ----------------------------------------
+    hailo_network_parameters_t {
+        batch_size: u16,
+    }
+    hailo_network_parameters_by_name_t {
+        name: String,
+        network_params: hailo_network_parameters_t,
+    }
+    hailo_activate_network_group_params_t {
+        _reserved: u8,
+    }
+    hailo_network_group_info_t {
+        name: String,
+        is_multi_context: bool,
+    }
+    hailo_layer_name_t {
+        name: String,
+    }
+    hailo_network_info_t {
+        name: String,
+    }
+    hailo_latency_measurement_result_t {
+        avg_hw_latency_ms: f64,
+    }
+    hailo_rate_limit_t {
+        stream_name: String,
+        rate: u32,
+    }
+    hailo_i2c_slave_config_t {
+        endianness: Endianness,
+        slave_address: u16,
+        register_address_size: u8,
+        bus_index: u8,
+        should_hold_bus: bool,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Union-bearing structs
+// ---------------------------------------------------------------------------
+
+const MISMATCHED_MEMBER: &str = "union member does not match the discriminating field";
+
+/// Member of `hailo_stream_params_union_t`.
+#[derive(Serialize, Deserialize)]
+enum StreamParams {
+    #[serde(rename = "pcie_input_params")]
+    PcieInput(hailo_pcie_input_stream_params_t),
+    #[serde(rename = "pcie_output_params")]
+    PcieOutput(hailo_pcie_output_stream_params_t),
+    #[serde(rename = "integrated_input_params")]
+    IntegratedInput(hailo_integrated_input_stream_params_t),
+    #[serde(rename = "integrated_output_params")]
+    IntegratedOutput(hailo_integrated_output_stream_params_t),
+}
+
+/// `params` is `None` for interfaces without a member in the union.
+#[derive(Serialize, Deserialize)]
+struct StreamParametersRepr {
+    stream_interface: StreamInterface,
+    direction: StreamDirection,
+    flags: StreamFlags,
+    params: Option<StreamParams>,
+}
+
+impl Serialize for hailo_stream_parameters_t {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // SAFETY: each arm reads the member that interface and direction
+        // select; all members are plain bytes.
+        let params = unsafe {
+            match (self.stream_interface, self.direction) {
+                (HAILO_STREAM_INTERFACE_PCIE, HAILO_H2D_STREAM) => {
+                    Some(StreamParams::PcieInput(self.params.pcie_input_params))
+                }
+                (HAILO_STREAM_INTERFACE_PCIE, HAILO_D2H_STREAM) => {
+                    Some(StreamParams::PcieOutput(self.params.pcie_output_params))
+                }
+                (HAILO_STREAM_INTERFACE_INTEGRATED, HAILO_H2D_STREAM) => Some(
+                    StreamParams::IntegratedInput(self.params.integrated_input_params),
+                ),
+                (HAILO_STREAM_INTERFACE_INTEGRATED, HAILO_D2H_STREAM) => Some(
+                    StreamParams::IntegratedOutput(self.params.integrated_output_params),
+                ),
+                _ => None,
+            }
+        };
+        StreamParametersRepr {
+            stream_interface: Field::encode(&self.stream_interface),
+            direction: Field::encode(&self.direction),
+            flags: Field::encode(&self.flags),
+            params,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for hailo_stream_parameters_t {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = StreamParametersRepr::deserialize(deserializer)?;
+        let stream_interface = repr.stream_interface.decode().map_err(D::Error::custom)?;
+        let direction = repr.direction.decode().map_err(D::Error::custom)?;
+        let params = match (stream_interface, direction, repr.params) {
+            (HAILO_STREAM_INTERFACE_PCIE, HAILO_H2D_STREAM, Some(StreamParams::PcieInput(p))) => {
+                hailo_stream_params_union_t {
+                    pcie_input_params: p,
+                }
+            }
+            (HAILO_STREAM_INTERFACE_PCIE, HAILO_D2H_STREAM, Some(StreamParams::PcieOutput(p))) => {
+                hailo_stream_params_union_t {
+                    pcie_output_params: p,
+                }
+            }
+            (
+                HAILO_STREAM_INTERFACE_INTEGRATED,
+                HAILO_H2D_STREAM,
+                Some(StreamParams::IntegratedInput(p)),
+            ) => hailo_stream_params_union_t {
+                integrated_input_params: p,
+            },
+            (
+                HAILO_STREAM_INTERFACE_INTEGRATED,
+                HAILO_D2H_STREAM,
+                Some(StreamParams::IntegratedOutput(p)),
+            ) => hailo_stream_params_union_t {
+                integrated_output_params: p,
+            },
+            (
+                HAILO_STREAM_INTERFACE_PCIE | HAILO_STREAM_INTERFACE_INTEGRATED,
+                HAILO_H2D_STREAM | HAILO_D2H_STREAM,
+                _,
+            )
+            | (_, _, Some(_)) => return Err(D::Error::custom(MISMATCHED_MEMBER)),
+            _ => hailo_stream_params_union_t {
+                pcie_input_params: hailo_pcie_input_stream_params_t { _reserved: 0 },
+            },
+        };
+        Ok(Self {
+            stream_interface,
+            direction,
+            flags: repr.flags.decode().map_err(D::Error::custom)?,
+            params,
+        })
+    }
+}
+
+/// Member of `hailo_stream_info_shape_t`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StreamInfoShape {
+    Shapes(hailo_stream_info_shape_pair_t),
+    NmsInfo(hailo_nms_info_t),
+}
+
+#[derive(Serialize, Deserialize)]
+struct StreamInfoRepr {
+    shape: StreamInfoShape,
+    hw_data_bytes: u32,
+    hw_frame_size: u32,
+    format: hailo_format_t,
+    direction: StreamDirection,
+    index: u8,
+    name: String,
+    quant_info: hailo_quant_info_t,
+    is_mux: bool,
+}
+
+impl Serialize for hailo_stream_info_t {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let shape = if is_nms_order(self.format.order) {
+            // SAFETY: NMS orders select the `nms_info` branch.
+            StreamInfoShape::NmsInfo(unsafe { self.shape.nms_info })
+        } else {
+            // SAFETY: every other order selects the `shapes` branch.
+            StreamInfoShape::Shapes(unsafe { self.shape.shapes })
+        };
+        StreamInfoRepr {
+            shape,
+            hw_data_bytes: self.hw_data_bytes,
+            hw_frame_size: self.hw_frame_size,
+            format: self.format,
+            direction: Field::encode(&self.direction),
+            index: self.index,
+            name: Field::encode(&self.name),
+            quant_info: self.quant_info,
+            is_mux: self.is_mux,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for hailo_stream_info_t {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = StreamInfoRepr::deserialize(deserializer)?;
+        let shape = match (is_nms_order(repr.format.order), repr.shape) {
+            (true, StreamInfoShape::NmsInfo(nms_info)) => hailo_stream_info_shape_t { nms_info },
+            (false, StreamInfoShape::Shapes(shapes)) => hailo_stream_info_shape_t { shapes },
+            _ => return Err(D::Error::custom(MISMATCHED_MEMBER)),
+        };
+        Ok(Self {
+            shape,
+            hw_data_bytes: repr.hw_data_bytes,
+            hw_frame_size: repr.hw_frame_size,
+            format: repr.format,
+            direction: repr.direction.decode().map_err(D::Error::custom)?,
+            index: repr.index,
+            name: repr.name.decode().map_err(D::Error::custom)?,
+            quant_info: repr.quant_info,
+            is_mux: repr.is_mux,
+        })
+    }
+}
+
+/// Member of `hailo_vstream_info_shape_t`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum VStreamInfoShape {
+    Shape(hailo_3d_image_shape_t),
+    NmsShape(hailo_nms_shape_t),
+}
+
+#[derive(Serialize, Deserialize)]
+struct VStreamInfoRepr {
+    name: String,
+    network_name: String,
+    direction: StreamDirection,
+    format: hailo_format_t,
+    shape: VStreamInfoShape,
+    quant_info: hailo_quant_info_t,
+}
+
+impl Serialize for hailo_vstream_info_t {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let shape = if is_nms_order(self.format.order) {
+            // SAFETY: NMS orders select the `nms_shape` branch.
+            VStreamInfoShape::NmsShape(unsafe { self.shape.nms_shape })
+        } else {
+            // SAFETY: every other order selects the `shape` branch.
+            VStreamInfoShape::Shape(unsafe { self.shape.shape })
+        };
+        VStreamInfoRepr {
+            name: Field::encode(&self.name),
+            network_name: Field::encode(&self.network_name),
+            direction: Field::encode(&self.direction),
+            format: self.format,
+            shape,
+            quant_info: self.quant_info,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for hailo_vstream_info_t {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = VStreamInfoRepr::deserialize(deserializer)?;
+        let shape = match (is_nms_order(repr.format.order), repr.shape) {
+            (true, VStreamInfoShape::NmsShape(nms_shape)) => {
+                hailo_vstream_info_shape_t { nms_shape }
+            }
+            (false, VStreamInfoShape::Shape(shape)) => hailo_vstream_info_shape_t { shape },
+            _ => return Err(D::Error::custom(MISMATCHED_MEMBER)),
+        };
+        Ok(Self {
+            name: repr.name.decode().map_err(D::Error::custom)?,
+            network_name: repr.network_name.decode().map_err(D::Error::custom)?,
+            direction: repr.direction.decode().map_err(D::Error::custom)?,
+            format: repr.format,
+            shape,
+            quant_info: repr.quant_info,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::Notification;
+    use serde_json::json;
+
+    fn round_trip<T: Serialize + for<'de> Deserialize<'de>>(value: &T) -> serde_json::Value {
+        let json = serde_json::to_value(value).unwrap();
+        let back: T = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&back).unwrap(), json);
+        json
+    }
+
+    #[test]
+    fn names_and_enums_are_symbolic() {
+        // SAFETY: all-zero is valid for this plain-data struct.
+        let mut identity: hailo_device_identity_t = unsafe { std::mem::zeroed() };
+        identity.board_name.set("Hailo-8").unwrap();
+        identity.device_architecture = HAILO_ARCH_HAILO8L;
+        let json = round_trip(&identity);
+        assert_eq!(json["board_name"], "Hailo-8");
+        assert_eq!(json["device_architecture"], "hailo8l");
+
+        // A serial number may use the whole field, with no room for a NUL.
+        for (dst, src) in identity.serial_number.iter_mut().zip(b"HLLWMB0123456789") {
+            *dst = *src as c_char;
+        }
+        identity.serial_number_length = 16;
+        let json = round_trip(&identity);
+        assert_eq!(json["serial_number"], "HLLWMB0123456789");
+        assert_eq!(json["serial_number_length"], 16);
+        assert_eq!(
+            json["fw_version"],
+            json!({"major": 0, "minor": 0, "revision": 0})
+        );
+
+        let params = hailo_vstream_params_t {
+            vstream_stats_flags: HAILO_VSTREAM_STATS_MEASURE_FPS
+                | HAILO_VSTREAM_STATS_MEASURE_LATENCY,
+            ..Default::default()
+        };
+        let json = round_trip(&params);
+        assert_eq!(json["user_buffer_format"]["order"], "auto");
+        assert_eq!(json["vstream_stats_flags"], "measure_fps | measure_latency");
+        assert_eq!(json["pipeline_elements_stats_flags"], "none");
+
+        // SAFETY: as above.
+        let mut health: hailo_health_info_t = unsafe { std::mem::zeroed() };
+        health.current_temperature_zone = 7;
+        let json = round_trip(&health);
+        assert_eq!(json["current_temperature_zone"], "unknown (7)");
+        assert_eq!(json["current_overcurrent_zone"], "green");
+    }
+
+    #[test]
+    fn unions_use_their_decoded_member() {
+        // SAFETY: all-zero is valid for these plain-data structs.
+        let mut info: hailo_stream_info_t = unsafe { std::mem::zeroed() };
+        info.format.order = HAILO_FORMAT_ORDER_HAILO_NMS;
+        info.shape.nms_info.number_of_classes = 80;
+        let json = round_trip(&info);
+        assert_eq!(json["shape"]["nms_info"]["number_of_classes"], 80);
+        assert_eq!(json["shape"]["nms_info"]["burst_type"], "h8_bbox");
+
+        let mut bad = json.clone();
+        bad["format"]["order"] = json!("nhwc");
+        assert!(serde_json::from_value::<hailo_stream_info_t>(bad).is_err());
+
+        // SAFETY: as above.
+        let mut vinfo: hailo_vstream_info_t = unsafe { std::mem::zeroed() };
+        vinfo.format.order = HAILO_FORMAT_ORDER_NHWC;
+        vinfo.shape.shape.width = 640;
+        assert_eq!(round_trip(&vinfo)["shape"]["shape"]["width"], 640);
+
+        // SAFETY: as above.
+        let mut params: hailo_stream_parameters_t = unsafe { std::mem::zeroed() };
+        params.stream_interface = HAILO_STREAM_INTERFACE_PCIE;
+        params.direction = HAILO_D2H_STREAM;
+        params.flags = HAILO_STREAM_FLAGS_ASYNC;
+        let json = round_trip(&params);
+        assert_eq!(
+            json["params"],
+            json!({"pcie_output_params": {"_reserved": 0}})
+        );
+        assert_eq!(json["flags"], "async");
+    }
+
+    #[test]
+    fn invalid_values_are_rejected() {
+        let long = "n".repeat(200);
+        assert!(serde_json::from_value::<hailo_layer_name_t>(json!({ "name": long })).is_err());
+        let bad_order = json!({"type_": "uint8", "order": "sideways", "flags": "none"});
+        assert!(serde_json::from_value::<hailo_format_t>(bad_order).is_err());
+        let out_of_range = json!({ "new_state": "70000" });
+        assert!(
+            serde_json::from_value::<hailo_throttling_state_change_message_t>(out_of_range)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn notifications_serialize_decoded() {
+        let notification = Notification::ThrottlingStateChange {
+            new_state: ThrottlingState::Heavy,
+        };
+        let json = serde_json::to_value(notification).unwrap();
+        assert_eq!(
+            json,
+            json!({"throttling_state_change": {"new_state": "heavy"}})
+        );
+        assert_eq!(
+            serde_json::from_value::<Notification>(json).unwrap(),
+            notification
+        );
+    }
+}
@@ -0,0 +1,976 @@
+//! Rust enums and flag sets parallel to the `c_int` aliases in [`enums`].
+//!
+//! Each alias `hailo_foo_t` gets a `#[repr(i32)]`, `#[non_exhaustive]` enum
+//! whose `Unknown(c_int)` variant keeps values added by newer runtimes, so
+//! `From<c_int>` (and hence `TryFrom<c_int>`) never fails and converting back
+//! is lossless.  `Display` prints the lowercase C suffix (`"nhwc"`,
+//! `"uint8"`, `"unknown (42)"`) and `FromStr` accepts the same strings or a
+//! bare integer.
+//!
+//! Flag aliases become transparent bit sets with associated constants,
+//! set operators and `a | b` formatting; unnamed bits are retained.
+//!
+//! Enums used by a single safe module are declared there (for example
+//! [`TemperatureZone`](crate::TemperatureZone) in `health` and
+//! [`CpuId`](crate::CpuId) in `watchdog`) with the same macro.
+//!
+//! The `Debug` impls of the union-bearing structs in [`types`] live here
+//! too, so they can name the discriminants they decode.
+//!
+//! [`enums`]: crate::enums
+//! [`types`]: crate::types
+
+use std::fmt;
+
+use crate::constants::MAX_NUMBER_OF_PLANES;
+use crate::cstr::CFixedStr;
+use crate::enums::*;
+use crate::types::{
+    hailo_format_t, hailo_pix_buffer_plane_t, hailo_pix_buffer_t, hailo_stream_info_t,
+    hailo_stream_parameters_t, hailo_vstream_info_t,
+};
+
+/// Declares a `c_int`-backed enum with an `Unknown(c_int)` fallback.
+macro_rules! c_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident: $raw:ty {
+            $($(#[$vmeta:meta])* $variant:ident = $value:expr => $text:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone)]
+        #[repr(i32)]
+        #[non_exhaustive]
+        $vis enum $name {
+            $($(#[$vmeta])* $variant = $value,)*
+            /// A value this crate has no name for.  Equality and hashing go
+            /// through [`as_raw`](Self::as_raw), so `Unknown` holding a named
+            /// value equals that variant, but only `From<c_int>` normalises
+            /// it for `match`.
+            Unknown(std::os::raw::c_int) = i32::MIN,
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.as_raw() == other.as_raw()
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl std::hash::Hash for $name {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.as_raw().hash(state);
+            }
+        }
+
+        impl $name {
+            /// Every named variant.
+            pub const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+            pub const fn as_raw(self) -> $raw {
+                match self {
+                    $(Self::$variant => $value,)*
+                    Self::Unknown(raw) => raw,
+                }
+            }
+        }
+
+        impl From<std::os::raw::c_int> for $name {
+            fn from(raw: std::os::raw::c_int) -> Self {
+                match raw {
+                    $(raw if raw == $value => Self::$variant,)*
+                    other => Self::Unknown(other),
+                }
+            }
+        }
+
+        impl From<$name> for std::os::raw::c_int {
+            fn from(value: $name) -> Self {
+                value.as_raw()
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(Self::$variant => f.write_str($text),)*
+                    Self::Unknown(raw) => write!(f, "unknown ({raw})"),
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let text = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+                text.parse()
+                    .map_err(|_| serde::de::Error::custom(format_args!("unknown {} `{text}`", stringify!($name))))
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = crate::error::HailoError;
+
+            /// Accepts the `Display` form (ASCII case-insensitive) or an
+            /// integer.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s = s.trim();
+                $(if s.eq_ignore_ascii_case($text) {
+                    return Ok(Self::$variant);
+                })*
+                let number = s
+                    .strip_prefix("unknown (")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                    .unwrap_or(s);
+                number
+                    .parse::<std::os::raw::c_int>()
+                    .map(Self::from)
+                    .map_err(|_| crate::error::HailoError::InvalidArgument)
+            }
+        }
+    };
+}
+
+/// Declares a transparent `c_int` bit set with named flags.
+macro_rules! c_flags {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident: $raw:ty {
+            $($(#[$fmeta:meta])* const $flag:ident = $value:expr => $text:literal;)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+        #[repr(transparent)]
+        $vis struct $name($raw);
+
+        impl $name {
+            $($(#[$fmeta])* pub const $flag: Self = Self($value);)*
+
+            const NAMED: &'static [(Self, &'static str)] = &[$((Self::$flag, $text)),*];
+
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            /// Every named flag.
+            pub const fn all() -> Self {
+                Self(0 $(| $value)*)
+            }
+
+            pub const fn bits(self) -> $raw {
+                self.0
+            }
+
+            /// Keeps every bit, including ones this crate has no name for.
+            pub const fn from_bits_retain(bits: $raw) -> Self {
+                Self(bits)
+            }
+
+            /// Bits without a named flag.
+            pub const fn unknown_bits(self) -> $raw {
+                self.0 & !Self::all().0
+            }
+
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            pub const fn intersects(self, other: Self) -> bool {
+                self.0 & other.0 != 0
+            }
+
+            pub fn insert(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+
+            pub fn remove(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+        }
+
+        impl From<std::os::raw::c_int> for $name {
+            fn from(bits: std::os::raw::c_int) -> Self {
+                Self(bits)
+            }
+        }
+
+        impl From<$name> for std::os::raw::c_int {
+            fn from(flags: $name) -> Self {
+                flags.0
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = Self;
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl std::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.0 |= rhs.0;
+            }
+        }
+
+        impl std::ops::BitAnd for $name {
+            type Output = Self;
+            fn bitand(self, rhs: Self) -> Self {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl std::ops::BitAndAssign for $name {
+            fn bitand_assign(&mut self, rhs: Self) {
+                self.0 &= rhs.0;
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            /// `none`, or named flags and any remaining bits joined by ` | `.
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if self.is_empty() {
+                    return f.write_str("none");
+                }
+                let mut first = true;
+                let mut sep = |f: &mut std::fmt::Formatter<'_>| {
+                    let s = if first { "" } else { " | " };
+                    first = false;
+                    f.write_str(s)
+                };
+                for &(flag, text) in Self::NAMED {
+                    if self.contains(flag) {
+                        sep(f)?;
+                        f.write_str(text)?;
+                    }
+                }
+                if self.unknown_bits() != 0 {
+                    sep(f)?;
+                    write!(f, "{:#x}", self.unknown_bits())?;
+                }
+                Ok(())
+            }
+        }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}({self})", stringify!($name))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let text = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+                text.parse()
+                    .map_err(|_| serde::de::Error::custom(format_args!("invalid {} `{text}`", stringify!($name))))
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = crate::error::HailoError;
+
+            /// Parses `Display` output: flag names (ASCII case-insensitive)
+            /// or integers joined by `|`, or `none`.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut flags = Self::empty();
+                for part in s.split('|').map(str::trim) {
+                    if part.eq_ignore_ascii_case("none") {
+                        continue;
+                    }
+                    if let Some(&(flag, _)) =
+                        Self::NAMED.iter().find(|(_, text)| part.eq_ignore_ascii_case(text))
+                    {
+                        flags |= flag;
+                        continue;
+                    }
+                    let bits = match part.strip_prefix("0x") {
+                        Some(hex) => std::os::raw::c_int::from_str_radix(hex, 16),
+                        None => part.parse(),
+                    };
+                    flags |= Self(bits.map_err(|_| crate::error::HailoError::InvalidArgument)?);
+                }
+                Ok(flags)
+            }
+        }
+    };
+}
+
+pub(crate) use c_enum;
+
+// --- Power and measurement --------------------------------------------------
+
+c_enum! {
+    /// `hailo_dvm_options_t`.
+    pub enum DvmOption: hailo_dvm_options_t {
+        VddCore = HAILO_DVM_OPTIONS_VDD_CORE => "vdd_core",
+        VddIo = HAILO_DVM_OPTIONS_VDD_IO => "vdd_io",
+        MipiAvdd = HAILO_DVM_OPTIONS_MIPI_AVDD => "mipi_avdd",
+        MipiAvddH = HAILO_DVM_OPTIONS_MIPI_AVDD_H => "mipi_avdd_h",
+        UsbAvddIo = HAILO_DVM_OPTIONS_USB_AVDD_IO => "usb_avdd_io",
+        VddTop = HAILO_DVM_OPTIONS_VDD_TOP => "vdd_top",
+        UsbAvddIoHv = HAILO_DVM_OPTIONS_USB_AVDD_IO_HV => "usb_avdd_io_hv",
+        AvddH = HAILO_DVM_OPTIONS_AVDD_H => "avdd_h",
+        SdioVddIo = HAILO_DVM_OPTIONS_SDIO_VDD_IO => "sdio_vdd_io",
+        OvercurrentProtection = HAILO_DVM_OPTIONS_OVERCURRENT_PROTECTION => "overcurrent_protection",
+        Auto = HAILO_DVM_OPTIONS_AUTO => "auto",
+    }
+}
+
+c_enum! {
+    /// `hailo_power_measurement_types_t`.
+    pub enum PowerMeasurementType: hailo_power_measurement_types_t {
+        ShuntVoltage = HAILO_POWER_MEASUREMENT_TYPES__SHUNT_VOLTAGE => "shunt_voltage",
+        BusVoltage = HAILO_POWER_MEASUREMENT_TYPES__BUS_VOLTAGE => "bus_voltage",
+        Power = HAILO_POWER_MEASUREMENT_TYPES__POWER => "power",
+        Current = HAILO_POWER_MEASUREMENT_TYPES__CURRENT => "current",
+        Auto = HAILO_POWER_MEASUREMENT_TYPES__AUTO => "auto",
+    }
+}
+
+c_enum! {
+    /// `hailo_sampling_period_t`.
+    pub enum SamplingPeriod: hailo_sampling_period_t {
+        Us140 = HAILO_SAMPLING_PERIOD_140US => "140us",
+        Us204 = HAILO_SAMPLING_PERIOD_204US => "204us",
+        Us332 = HAILO_SAMPLING_PERIOD_332US => "332us",
+        Us588 = HAILO_SAMPLING_PERIOD_588US => "588us",
+        Us1100 = HAILO_SAMPLING_PERIOD_1100US => "1100us",
+        Us2116 = HAILO_SAMPLING_PERIOD_2116US => "2116us",
+        Us4156 = HAILO_SAMPLING_PERIOD_4156US => "4156us",
+        Us8244 = HAILO_SAMPLING_PERIOD_8244US => "8244us",
+    }
+}
+
+c_enum! {
+    /// `hailo_averaging_factor_t`.
+    pub enum AveragingFactor: hailo_averaging_factor_t {
+        X1 = HAILO_AVERAGE_FACTOR_1 => "1",
+        X4 = HAILO_AVERAGE_FACTOR_4 => "4",
+        X16 = HAILO_AVERAGE_FACTOR_16 => "16",
+        X64 = HAILO_AVERAGE_FACTOR_64 => "64",
+        X128 = HAILO_AVERAGE_FACTOR_128 => "128",
+        X256 = HAILO_AVERAGE_FACTOR_256 => "256",
+        X512 = HAILO_AVERAGE_FACTOR_512 => "512",
+        X1024 = HAILO_AVERAGE_FACTOR_1024 => "1024",
+    }
+}
+
+c_enum! {
+    /// `hailo_measurement_buffer_index_t`.
+    pub enum MeasurementBufferIndex: hailo_measurement_buffer_index_t {
+        Index0 = HAILO_MEASUREMENT_BUFFER_INDEX_0 => "0",
+        Index1 = HAILO_MEASUREMENT_BUFFER_INDEX_1 => "1",
+        Index2 = HAILO_MEASUREMENT_BUFFER_INDEX_2 => "2",
+        Index3 = HAILO_MEASUREMENT_BUFFER_INDEX_3 => "3",
+    }
+}
+
+c_enum! {
+    /// `hailo_power_mode_t`.
+    pub enum PowerMode: hailo_power_mode_t {
+        Performance = HAILO_POWER_MODE_PERFORMANCE => "performance",
+        UltraPerformance = HAILO_POWER_MODE_ULTRA_PERFORMANCE => "ultra_performance",
+    }
+}
+
+// --- Device identity --------------------------------------------------------
+
+c_enum! {
+    /// `hailo_device_type_t`.
+    pub enum DeviceType: hailo_device_type_t {
+        Pcie = HAILO_DEVICE_TYPE_PCIE => "pcie",
+        Eth = HAILO_DEVICE_TYPE_ETH => "eth",
+        Integrated = HAILO_DEVICE_TYPE_INTEGRATED => "integrated",
+    }
+}
+
+c_enum! {
+    /// `hailo_device_architecture_t`.
+    pub enum DeviceArchitecture: hailo_device_architecture_t {
+        Hailo8A0 = HAILO_ARCH_HAILO8_A0 => "hailo8_a0",
+        Hailo8 = HAILO_ARCH_HAILO8 => "hailo8",
+        Hailo8L = HAILO_ARCH_HAILO8L => "hailo8l",
+        Hailo15H = HAILO_ARCH_HAILO15H => "hailo15h",
+        Hailo15L = HAILO_ARCH_HAILO15L => "hailo15l",
+        Hailo15M = HAILO_ARCH_HAILO15M => "hailo15m",
+        Hailo10H = HAILO_ARCH_HAILO10H => "hailo10h",
+        Hailo12L = HAILO_ARCH_HAILO12L => "hailo12l",
+    }
+}
+
+c_enum! {
+    /// `hailo_device_boot_source_t`.
+    pub enum DeviceBootSource: hailo_device_boot_source_t {
+        Invalid = HAILO_DEVICE_BOOT_SOURCE_INVALID => "invalid",
+        Pcie = HAILO_DEVICE_BOOT_SOURCE_PCIE => "pcie",
+        Flash = HAILO_DEVICE_BOOT_SOURCE_FLASH => "flash",
+    }
+}
+
+c_enum! {
+    /// `hailo_scheduling_algorithm_t`.
+    pub enum SchedulingAlgorithm: hailo_scheduling_algorithm_t {
+        None = HAILO_SCHEDULING_ALGORITHM_NONE => "none",
+        RoundRobin = HAILO_SCHEDULING_ALGORITHM_ROUND_ROBIN => "round_robin",
+    }
+}
+
+c_enum! {
+    /// `hailo_reset_device_mode_t`.
+    pub enum ResetDeviceMode: hailo_reset_device_mode_t {
+        Chip = HAILO_RESET_DEVICE_MODE_CHIP => "chip",
+        NnCore = HAILO_RESET_DEVICE_MODE_NN_CORE => "nn_core",
+        Soft = HAILO_RESET_DEVICE_MODE_SOFT => "soft",
+        ForcedSoft = HAILO_RESET_DEVICE_MODE_FORCED_SOFT => "forced_soft",
+        Reboot = HAILO_RESET_DEVICE_MODE_REBOOT => "reboot",
+    }
+}
+
+// --- Data format ------------------------------------------------------------
+
+c_enum! {
+    /// `hailo_format_type_t`.
+    pub enum FormatType: hailo_format_type_t {
+        Auto = HAILO_FORMAT_TYPE_AUTO => "auto",
+        Uint8 = HAILO_FORMAT_TYPE_UINT8 => "uint8",
+        Uint16 = HAILO_FORMAT_TYPE_UINT16 => "uint16",
+        Float32 = HAILO_FORMAT_TYPE_FLOAT32 => "float32",
+    }
+}
+
+c_enum! {
+    /// `hailo_format_order_t`.
+    pub enum FormatOrder: hailo_format_order_t {
+        Auto = HAILO_FORMAT_ORDER_AUTO => "auto",
+        Nhwc = HAILO_FORMAT_ORDER_NHWC => "nhwc",
+        Nhcw = HAILO_FORMAT_ORDER_NHCW => "nhcw",
+        Fcr = HAILO_FORMAT_ORDER_FCR => "fcr",
+        F8cr = HAILO_FORMAT_ORDER_F8CR => "f8cr",
+        Nhw = HAILO_FORMAT_ORDER_NHW => "nhw",
+        Nc = HAILO_FORMAT_ORDER_NC => "nc",
+        BayerRgb = HAILO_FORMAT_ORDER_BAYER_RGB => "bayer_rgb",
+        Bayer12BitRgb = HAILO_FORMAT_ORDER_12_BIT_BAYER_RGB => "12_bit_bayer_rgb",
+        HailoNms = HAILO_FORMAT_ORDER_HAILO_NMS => "hailo_nms",
+        Rgb888 = HAILO_FORMAT_ORDER_RGB888 => "rgb888",
+        Nchw = HAILO_FORMAT_ORDER_NCHW => "nchw",
+        Yuy2 = HAILO_FORMAT_ORDER_YUY2 => "yuy2",
+        Nv12 = HAILO_FORMAT_ORDER_NV12 => "nv12",
+        Nv21 = HAILO_FORMAT_ORDER_NV21 => "nv21",
+        HailoYyuv = HAILO_FORMAT_ORDER_HAILO_YYUV => "hailo_yyuv",
+        HailoYyvu = HAILO_FORMAT_ORDER_HAILO_YYVU => "hailo_yyvu",
+        Rgb4 = HAILO_FORMAT_ORDER_RGB4 => "rgb4",
+        I420 = HAILO_FORMAT_ORDER_I420 => "i420",
+        HailoYyyyuv = HAILO_FORMAT_ORDER_HAILO_YYYYUV => "hailo_yyyyuv",
+        HailoNmsWithByteMask = HAILO_FORMAT_ORDER_HAILO_NMS_WITH_BYTE_MASK => "hailo_nms_with_byte_mask",
+        HailoNmsOnChip = HAILO_FORMAT_ORDER_HAILO_NMS_ON_CHIP => "hailo_nms_on_chip",
+        HailoNmsByClass = HAILO_FORMAT_ORDER_HAILO_NMS_BY_CLASS => "hailo_nms_by_class",
+        HailoNmsByScore = HAILO_FORMAT_ORDER_HAILO_NMS_BY_SCORE => "hailo_nms_by_score",
+    }
+}
+
+c_flags! {
+    /// `hailo_format_flags_t`.
+    pub struct FormatFlags: hailo_format_flags_t {
+        const QUANTIZED = HAILO_FORMAT_FLAGS_QUANTIZED => "quantized";
+        const TRANSPOSED = HAILO_FORMAT_FLAGS_TRANSPOSED => "transposed";
+    }
+}
+
+/// `hailo_format_t` with typed fields.  Defaults to `AUTO` type and order
+/// with no flags.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Format {
+    pub format_type: FormatType,
+    pub order: FormatOrder,
+    pub flags: FormatFlags,
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        hailo_format_t::default().into()
+    }
+}
+
+impl From<hailo_format_t> for Format {
+    fn from(raw: hailo_format_t) -> Self {
+        Self {
+            format_type: raw.type_.into(),
+            order: raw.order.into(),
+            flags: raw.flags.into(),
+        }
+    }
+}
+
+impl From<Format> for hailo_format_t {
+    fn from(format: Format) -> Self {
+        Self {
+            type_: format.format_type.into(),
+            order: format.order.into(),
+            flags: format.flags.into(),
+        }
+    }
+}
+
+// --- Streams ----------------------------------------------------------------
+
+c_enum! {
+    /// `hailo_stream_transform_mode_t`.
+    pub enum StreamTransformMode: hailo_stream_transform_mode_t {
+        NoTransform = HAILO_STREAM_NO_TRANSFORM => "no_transform",
+        TransformCopy = HAILO_STREAM_TRANSFORM_COPY => "transform_copy",
+    }
+}
+
+c_enum! {
+    /// `hailo_stream_direction_t`.
+    pub enum StreamDirection: hailo_stream_direction_t {
+        H2d = HAILO_H2D_STREAM => "h2d",
+        D2h = HAILO_D2H_STREAM => "d2h",
+    }
+}
+
+c_flags! {
+    /// `hailo_stream_flags_t`.
+    pub struct StreamFlags: hailo_stream_flags_t {
+        const ASYNC = HAILO_STREAM_FLAGS_ASYNC => "async";
+    }
+}
+
+c_enum! {
+    /// `hailo_stream_interface_t`.
+    pub enum StreamInterface: hailo_stream_interface_t {
+        Pcie = HAILO_STREAM_INTERFACE_PCIE => "pcie",
+        Eth = HAILO_STREAM_INTERFACE_ETH => "eth",
+        Mipi = HAILO_STREAM_INTERFACE_MIPI => "mipi",
+        Integrated = HAILO_STREAM_INTERFACE_INTEGRATED => "integrated",
+    }
+}
+
+c_flags! {
+    /// `hailo_latency_measurement_flags_t`.
+    pub struct LatencyMeasurementFlags: hailo_latency_measurement_flags_t {
+        const MEASURE = HAILO_LATENCY_MEASURE => "measure";
+        const CLEAR_AFTER_GET = HAILO_LATENCY_CLEAR_AFTER_GET => "clear_after_get";
+    }
+}
+
+// --- Virtual streams --------------------------------------------------------
+
+c_flags! {
+    /// `hailo_vstream_stats_flags_t`.
+    pub struct VStreamStatsFlags: hailo_vstream_stats_flags_t {
+        const MEASURE_FPS = HAILO_VSTREAM_STATS_MEASURE_FPS => "measure_fps";
+        const MEASURE_LATENCY = HAILO_VSTREAM_STATS_MEASURE_LATENCY => "measure_latency";
+    }
+}
+
+c_flags! {
+    /// `hailo_pipeline_elem_stats_flags_t`.
+    pub struct PipelineElemStatsFlags: hailo_pipeline_elem_stats_flags_t {
+        const MEASURE_FPS = HAILO_PIPELINE_ELEM_STATS_MEASURE_FPS => "measure_fps";
+        const MEASURE_LATENCY = HAILO_PIPELINE_ELEM_STATS_MEASURE_LATENCY => "measure_latency";
+        const MEASURE_QUEUE_SIZE = HAILO_PIPELINE_ELEM_STATS_MEASURE_QUEUE_SIZE => "measure_queue_size";
+    }
+}
+
+// --- Buffers and DMA --------------------------------------------------------
+
+c_enum! {
+    /// `hailo_dma_buffer_direction_t`.
+    pub enum DmaBufferDirection: hailo_dma_buffer_direction_t {
+        H2d = HAILO_DMA_BUFFER_DIRECTION_H2D => "h2d",
+        D2h = HAILO_DMA_BUFFER_DIRECTION_D2H => "d2h",
+        Both = HAILO_DMA_BUFFER_DIRECTION_BOTH => "both",
+    }
+}
+
+c_enum! {
+    /// `hailo_buffer_flags_t`.  The C values are sequential, not bit flags.
+    pub enum BufferFlags: hailo_buffer_flags_t {
+        None = HAILO_BUFFER_FLAGS_NONE => "none",
+        Dma = HAILO_BUFFER_FLAGS_DMA => "dma",
+        Continuous = HAILO_BUFFER_FLAGS_CONTINUOUS => "continuous",
+        SharedMemory = HAILO_BUFFER_FLAGS_SHARED_MEMORY => "shared_memory",
+    }
+}
+
+c_enum! {
+    /// `hailo_pix_buffer_memory_type_t`.
+    pub enum PixBufferMemoryType: hailo_pix_buffer_memory_type_t {
+        Userptr = HAILO_PIX_BUFFER_MEMORY_TYPE_USERPTR => "userptr",
+        Dmabuf = HAILO_PIX_BUFFER_MEMORY_TYPE_DMABUF => "dmabuf",
+    }
+}
+
+// --- NMS detection ----------------------------------------------------------
+
+c_enum! {
+    /// `hailo_nms_burst_type_t`.
+    pub enum NmsBurstType: hailo_nms_burst_type_t {
+        H8Bbox = HAILO_BURST_TYPE_H8_BBOX => "h8_bbox",
+        H15Bbox = HAILO_BURST_TYPE_H15_BBOX => "h15_bbox",
+        H8PerClass = HAILO_BURST_TYPE_H8_PER_CLASS => "h8_per_class",
+        H15PerClass = HAILO_BURST_TYPE_H15_PER_CLASS => "h15_per_class",
+        H15PerFrame = HAILO_BURST_TYPE_H15_PER_FRAME => "h15_per_frame",
+    }
+}
+
+// --- Notifications ----------------------------------------------------------
+
+c_enum! {
+    /// `hailo_notification_id_t`.
+    pub enum NotificationId: hailo_notification_id_t {
+        EthernetRxError = HAILO_NOTIFICATION_ID_ETHERNET_RX_ERROR => "ethernet_rx_error",
+        TemperatureAlarm = HAILO_NOTIFICATION_ID_HEALTH_MONITOR_TEMPERATURE_ALARM => "health_monitor_temperature_alarm",
+        DataflowShutdown = HAILO_NOTIFICATION_ID_HEALTH_MONITOR_DATAFLOW_SHUTDOWN => "health_monitor_dataflow_shutdown",
+        OvercurrentAlarm = HAILO_NOTIFICATION_ID_HEALTH_MONITOR_OVERCURRENT_ALARM => "health_monitor_overcurrent_alarm",
+        LcuEccCorrectableError = HAILO_NOTIFICATION_ID_LCU_ECC_CORRECTABLE_ERROR => "lcu_ecc_correctable_error",
+        LcuEccUncorrectableError = HAILO_NOTIFICATION_ID_LCU_ECC_UNCORRECTABLE_ERROR => "lcu_ecc_uncorrectable_error",
+        CpuEccError = HAILO_NOTIFICATION_ID_CPU_ECC_ERROR => "cpu_ecc_error",
+        CpuEccFatal = HAILO_NOTIFICATION_ID_CPU_ECC_FATAL => "cpu_ecc_fatal",
+        Debug = HAILO_NOTIFICATION_ID_DEBUG => "debug",
+        ContextSwitchBreakpointReached = HAILO_NOTIFICATION_ID_CONTEXT_SWITCH_BREAKPOINT_REACHED => "context_switch_breakpoint_reached",
+        ClockChanged = HAILO_NOTIFICATION_ID_HEALTH_MONITOR_CLOCK_CHANGED_EVENT => "health_monitor_clock_changed_event",
+        InferDone = HAILO_NOTIFICATION_ID_HW_INFER_MANAGER_INFER_DONE => "hw_infer_manager_infer_done",
+        ContextSwitchRunTimeError = HAILO_NOTIFICATION_ID_CONTEXT_SWITCH_RUN_TIME_ERROR_EVENT => "context_switch_run_time_error_event",
+        NnCoreCrcError = HAILO_NOTIFICATION_ID_NN_CORE_CRC_ERROR_EVENT => "nn_core_crc_error_event",
+        ThrottlingStateChange = HAILO_NOTIFICATION_ID_THROTTLING_STATE_CHANGE_EVENT => "throttling_state_change_event",
+    }
+}
+
+// --- Firmware logging -------------------------------------------------------
+
+c_enum! {
+    /// `hailo_fw_logger_interface_t`.
+    pub enum FwLoggerInterface: hailo_fw_logger_interface_t {
+        Pcie = HAILO_FW_LOGGER_INTERFACE_PCIE => "pcie",
+        Uart = HAILO_FW_LOGGER_INTERFACE_UART => "uart",
+    }
+}
+
+c_enum! {
+    /// `hailo_fw_logger_level_t`.
+    pub enum FwLoggerLevel: hailo_fw_logger_level_t {
+        Trace = HAILO_FW_LOGGER_LEVEL_TRACE => "trace",
+        Debug = HAILO_FW_LOGGER_LEVEL_DEBUG => "debug",
+        Info = HAILO_FW_LOGGER_LEVEL_INFO => "info",
+        Warn = HAILO_FW_LOGGER_LEVEL_WARN => "warn",
+        Error = HAILO_FW_LOGGER_LEVEL_ERROR => "error",
+        Fatal = HAILO_FW_LOGGER_LEVEL_FATAL => "fatal",
+    }
+}
+
+// --- Debug for union-bearing structs ----------------------------------------
+//
+// The structs live in `types`; their `Debug` impls decode the union member
+// that the discriminants above select.
+
+impl fmt::Debug for hailo_stream_parameters_t {
+    /// Shows the `params` member selected by `stream_interface` and
+    /// `direction`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("hailo_stream_parameters_t");
+        s.field(
+            "stream_interface",
+            &StreamInterface::from(self.stream_interface),
+        )
+        .field("direction", &StreamDirection::from(self.direction))
+        .field("flags", &StreamFlags::from(self.flags));
+        // SAFETY: each arm reads the member that interface and direction
+        // select; all members are plain bytes.
+        unsafe {
+            match (self.stream_interface, self.direction) {
+                (HAILO_STREAM_INTERFACE_PCIE, HAILO_H2D_STREAM) => {
+                    s.field("pcie_input_params", &self.params.pcie_input_params)
+                }
+                (HAILO_STREAM_INTERFACE_PCIE, HAILO_D2H_STREAM) => {
+                    s.field("pcie_output_params", &self.params.pcie_output_params)
+                }
+                (HAILO_STREAM_INTERFACE_INTEGRATED, HAILO_H2D_STREAM) => s.field(
+                    "integrated_input_params",
+                    &self.params.integrated_input_params,
+                ),
+                (HAILO_STREAM_INTERFACE_INTEGRATED, HAILO_D2H_STREAM) => s.field(
+                    "integrated_output_params",
+                    &self.params.integrated_output_params,
+                ),
+                _ => s.field("params", &format_args!("<not decoded>")),
+            };
+        }
+        s.finish()
+    }
+}
+
+/// A plane formatted with the pointer member `memory_type` selects.
+struct PixPlaneDebug<'a> {
+    plane: &'a hailo_pix_buffer_plane_t,
+    memory_type: hailo_pix_buffer_memory_type_t,
+}
+
+impl fmt::Debug for PixPlaneDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("hailo_pix_buffer_plane_t");
+        s.field("bytes_used", &self.plane.bytes_used)
+            .field("plane_size", &self.plane.plane_size);
+        // SAFETY: `memory_type` selects the member; both are plain values.
+        unsafe {
+            match self.memory_type {
+                HAILO_PIX_BUFFER_MEMORY_TYPE_USERPTR => {
+                    s.field("user_ptr", &self.plane.ptr.user_ptr)
+                }
+                HAILO_PIX_BUFFER_MEMORY_TYPE_DMABUF => s.field("fd", &self.plane.ptr.fd),
+                _ => s.field("ptr", &format_args!("<not decoded>")),
+            };
+        }
+        s.finish()
+    }
+}
+
+impl fmt::Debug for hailo_pix_buffer_t {
+    /// Lists the first `number_of_planes` planes, each with `user_ptr` or
+    /// `fd` according to `memory_type`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = (self.number_of_planes as usize).min(MAX_NUMBER_OF_PLANES);
+        let planes: Vec<_> = self.planes[..count]
+            .iter()
+            .map(|plane| PixPlaneDebug {
+                plane,
+                memory_type: self.memory_type,
+            })
+            .collect();
+        f.debug_struct("hailo_pix_buffer_t")
+            .field("index", &self.index)
+            .field("memory_type", &PixBufferMemoryType::from(self.memory_type))
+            .field("number_of_planes", &self.number_of_planes)
+            .field("planes", &planes)
+            .finish()
+    }
+}
+
+impl fmt::Debug for hailo_stream_info_t {
+    /// Shows `nms_info` for NMS format orders, `shape` and `hw_shape`
+    /// otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("hailo_stream_info_t");
+        s.field("name", &self.name.to_string_lossy());
+        if is_nms_order(self.format.order) {
+            // SAFETY: NMS orders select the `nms_info` branch.
+            s.field("nms_info", unsafe { &self.shape.nms_info });
+        } else {
+            // SAFETY: every other order selects the `shapes` branch.
+            let shapes = unsafe { &self.shape.shapes };
+            s.field("shape", &shapes.shape)
+                .field("hw_shape", &shapes.hw_shape);
+        }
+        s.field("hw_data_bytes", &self.hw_data_bytes)
+            .field("hw_frame_size", &self.hw_frame_size)
+            .field("format", &self.format)
+            .field("direction", &StreamDirection::from(self.direction))
+            .field("index", &self.index)
+            .field("quant_info", &self.quant_info)
+            .field("is_mux", &self.is_mux)
+            .finish()
+    }
+}
+
+impl fmt::Debug for hailo_vstream_info_t {
+    /// Shows `nms_shape` for NMS format orders, `shape` otherwise.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("hailo_vstream_info_t");
+        s.field("name", &self.name.to_string_lossy())
+            .field("network_name", &self.network_name.to_string_lossy())
+            .field("direction", &StreamDirection::from(self.direction))
+            .field("format", &self.format);
+        if is_nms_order(self.format.order) {
+            // SAFETY: NMS orders select the `nms_shape` branch.
+            s.field("nms_shape", unsafe { &self.shape.nms_shape });
+        } else {
+            // SAFETY: every other order selects the `shape` branch.
+            s.field("shape", unsafe { &self.shape.shape });
+        }
+        s.field("quant_info", &self.quant_info).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::HailoError;
+    use crate::health::{OvercurrentZone, TemperatureZone, ThrottlingState};
+    use crate::i2c::Endianness;
+    use crate::sensor::SensorType;
+    use crate::watchdog::{CpuId, WatchdogMode};
+    use std::fmt::Display;
+    use std::os::raw::c_int;
+    use std::str::FromStr;
+
+    fn round_trips<T>(all: &[T])
+    where
+        T: Copy + PartialEq + std::fmt::Debug + Display + FromStr + From<c_int>,
+        c_int: From<T>,
+        <T as FromStr>::Err: std::fmt::Debug,
+    {
+        for &value in all {
+            assert_eq!(T::from(c_int::from(value)), value);
+            assert_eq!(value.to_string().parse::<T>().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn every_named_variant_round_trips() {
+        round_trips(DvmOption::ALL);
+        round_trips(PowerMeasurementType::ALL);
+        round_trips(SamplingPeriod::ALL);
+        round_trips(AveragingFactor::ALL);
+        round_trips(MeasurementBufferIndex::ALL);
+        round_trips(PowerMode::ALL);
+        round_trips(DeviceType::ALL);
+        round_trips(DeviceArchitecture::ALL);
+        round_trips(DeviceBootSource::ALL);
+        round_trips(CpuId::ALL);
+        round_trips(SchedulingAlgorithm::ALL);
+        round_trips(ResetDeviceMode::ALL);
+        round_trips(WatchdogMode::ALL);
+        round_trips(Endianness::ALL);
+        round_trips(FormatType::ALL);
+        round_trips(FormatOrder::ALL);
+        round_trips(StreamTransformMode::ALL);
+        round_trips(StreamDirection::ALL);
+        round_trips(StreamInterface::ALL);
+        round_trips(DmaBufferDirection::ALL);
+        round_trips(BufferFlags::ALL);
+        round_trips(PixBufferMemoryType::ALL);
+        round_trips(NmsBurstType::ALL);
+        round_trips(TemperatureZone::ALL);
+        round_trips(OvercurrentZone::ALL);
+        round_trips(ThrottlingState::ALL);
+        round_trips(NotificationId::ALL);
+        round_trips(SensorType::ALL);
+        round_trips(FwLoggerInterface::ALL);
+        round_trips(FwLoggerLevel::ALL);
+    }
+
+    #[test]
+    fn unknown_values_are_preserved() {
+        let order = FormatOrder::from(99);
+        assert_eq!(order, FormatOrder::Unknown(99));
+        assert_eq!(c_int::from(order), 99);
+        assert_eq!(order.to_string(), "unknown (99)");
+        assert_eq!("unknown (99)".parse(), Ok(order));
+        assert_eq!(
+            FormatType::from(HAILO_FORMAT_TYPE_FLOAT32),
+            FormatType::Float32
+        );
+        assert_eq!(DvmOption::from(c_int::MAX), DvmOption::Auto);
+    }
+
+    #[test]
+    fn hand_built_unknown_equals_named_variant() {
+        use std::collections::HashSet;
+
+        assert_eq!(
+            FormatType::Unknown(HAILO_FORMAT_TYPE_UINT8),
+            FormatType::Uint8
+        );
+        let set: HashSet<_> = [FormatOrder::Nhwc].into_iter().collect();
+        assert!(set.contains(&FormatOrder::Unknown(HAILO_FORMAT_ORDER_NHWC)));
+    }
+
+    #[test]
+    fn parsing_accepts_names_and_numbers() {
+        assert_eq!("NHWC".parse(), Ok(FormatOrder::Nhwc));
+        assert_eq!("3".parse(), Ok(FormatType::Float32));
+        assert_eq!(
+            "bogus".parse::<FormatType>(),
+            Err(HailoError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn flags_combine_and_keep_unknown_bits() {
+        let mut flags = VStreamStatsFlags::MEASURE_FPS | VStreamStatsFlags::MEASURE_LATENCY;
+        assert!(flags.contains(VStreamStatsFlags::MEASURE_FPS));
+        assert_eq!(flags, VStreamStatsFlags::all());
+        flags.remove(VStreamStatsFlags::MEASURE_FPS);
+        assert_eq!(flags.bits(), HAILO_VSTREAM_STATS_MEASURE_LATENCY);
+
+        let raw = FormatFlags::from(HAILO_FORMAT_FLAGS_QUANTIZED | 0x10);
+        assert_eq!(raw.unknown_bits(), 0x10);
+        assert_eq!(c_int::from(raw), 0x11);
+        assert_eq!(raw.to_string(), "quantized | 0x10");
+        assert_eq!("quantized | 0x10".parse(), Ok(raw));
+        assert_eq!(StreamFlags::empty().to_string(), "none");
+        assert_eq!("none".parse(), Ok(StreamFlags::empty()));
+        assert_eq!(
+            format!("{:?}", LatencyMeasurementFlags::MEASURE),
+            "LatencyMeasurementFlags(measure)"
+        );
+    }
+
+    #[test]
+    fn debug_decodes_shape_union_by_format_order() {
+        // SAFETY: all-zero is valid for these plain-data structs.
+        let mut info: hailo_stream_info_t = unsafe { std::mem::zeroed() };
+        info.name.set("yolov8/conv1").unwrap();
+        info.format.order = HAILO_FORMAT_ORDER_NHWC;
+        info.shape.shapes.shape.width = 640;
+        let text = format!("{info:?}");
+        assert!(text.contains("\"yolov8/conv1\""));
+        assert!(text.contains("hw_shape") && text.contains("width: 640"));
+        assert!(!text.contains("nms_info"));
+
+        info.format.order = HAILO_FORMAT_ORDER_HAILO_NMS_BY_CLASS;
+        info.shape.nms_info.number_of_classes = 80;
+        let text = format!("{info:?}");
+        assert!(text.contains("nms_info") && text.contains("number_of_classes: 80"));
+        assert!(!text.contains("hw_shape"));
+
+        // SAFETY: as above.
+        let mut vinfo: hailo_vstream_info_t = unsafe { std::mem::zeroed() };
+        vinfo.format.order = HAILO_FORMAT_ORDER_HAILO_NMS;
+        vinfo.shape.nms_shape.max_bboxes_total = 100;
+        assert!(format!("{vinfo:?}").contains("nms_shape: hailo_nms_shape_t"));
+    }
+
+    #[test]
+    fn debug_decodes_params_and_planes() {
+        // SAFETY: all-zero is valid for these plain-data structs.
+        let mut params: hailo_stream_parameters_t = unsafe { std::mem::zeroed() };
+        params.stream_interface = HAILO_STREAM_INTERFACE_PCIE;
+        params.direction = HAILO_D2H_STREAM;
+        params.flags = HAILO_STREAM_FLAGS_ASYNC;
+        let text = format!("{params:?}");
+        assert!(text.contains("pcie_output_params") && text.contains("StreamFlags(async)"));
+
+        // SAFETY: as above.
+        let mut pix: hailo_pix_buffer_t = unsafe { std::mem::zeroed() };
+        pix.memory_type = HAILO_PIX_BUFFER_MEMORY_TYPE_DMABUF;
+        pix.number_of_planes = 1;
+        pix.planes[0].ptr.fd = 7;
+        let text = format!("{pix:?}");
+        assert!(text.contains("Dmabuf") && text.contains("fd: 7"));
+        assert_eq!(text.matches("hailo_pix_buffer_plane_t").count(), 1);
+    }
+}
@@ -2,12 +2,18 @@
 //!
 //! These mirror the C struct and typedef declarations in `<hailo/hailort.h>`.
 //! Unions require `unsafe` to read; see the individual field docs.
+//! Structs that embed a union get a decoding `Debug` impl next to the typed
+//! enums that name their discriminants (in `typed_enums` and
+//! `notification`); parameter structs with header defaults implement
+//! `Default`.
 
 use std::os::raw::{c_char, c_float, c_double, c_int, c_uchar, c_void};
 
 use crate::constants::{
-    HAILO_ETH_MAC_LENGTH, HAILO_MAX_BOARD_NAME_LENGTH, HAILO_MAX_DEVICE_ID_LENGTH,
-    HAILO_MAX_NETWORK_GROUP_NAME_SIZE, HAILO_MAX_NETWORK_NAME_SIZE,
+    HAILO_DEFAULT_BATCH_SIZE, HAILO_DEFAULT_DEVICE_COUNT, HAILO_DEFAULT_VDEVICE_GROUP_ID,
+    HAILO_DEFAULT_VSTREAM_QUEUE_SIZE,
+    HAILO_DEFAULT_VSTREAM_TIMEOUT_MS, HAILO_ETH_MAC_LENGTH, HAILO_MAX_BOARD_NAME_LENGTH,
+    HAILO_MAX_DEVICE_ID_LENGTH, HAILO_MAX_NETWORK_GROUP_NAME_SIZE, HAILO_MAX_NETWORK_NAME_SIZE,
     HAILO_MAX_NETWORKS_IN_NETWORK_GROUP, HAILO_MAX_NETWORK_GROUPS, HAILO_MAX_PART_NUMBER_LENGTH,
     HAILO_MAX_PRODUCT_NAME_LENGTH, HAILO_MAX_SERIAL_NUMBER_LENGTH, HAILO_MAX_STREAM_NAME_SIZE,
     HAILO_MAX_STREAMS_COUNT, HAILO_MAX_TEMPERATURE_THROTTLING_LEVELS_NUMBER,
@@ -15,14 +21,17 @@ use crate::constants::{
     HAILO_UNIT_LEVEL_TRACKING_BYTES_LENGTH, MAX_NUMBER_OF_PLANES,
 };
 use crate::enums::{
+    HAILO_FORMAT_FLAGS_NONE, HAILO_FORMAT_ORDER_AUTO, HAILO_FORMAT_TYPE_AUTO,
+    HAILO_PIPELINE_ELEM_STATS_NONE, HAILO_SCHEDULING_ALGORITHM_ROUND_ROBIN,
+    HAILO_VSTREAM_STATS_NONE,
     hailo_buffer_flags_t, hailo_device_architecture_t, hailo_device_boot_source_t,
     hailo_endianness_t, hailo_format_flags_t, hailo_format_order_t, hailo_format_type_t,
-    hailo_latency_measurement_flags_t, hailo_nms_burst_type_t, hailo_notification_id_t,
-    hailo_overcurrent_protection_overcurrent_zone_t, hailo_pipeline_elem_stats_flags_t,
-    hailo_pix_buffer_memory_type_t, hailo_power_mode_t, hailo_scheduling_algorithm_t,
-    hailo_stream_direction_t, hailo_stream_flags_t, hailo_stream_interface_t,
-    hailo_stream_transform_mode_t, hailo_temperature_protection_temperature_zone_t,
-    hailo_vstream_stats_flags_t,
+    hailo_latency_measurement_flags_t, hailo_nms_burst_type_t,
+    hailo_notification_id_t, hailo_overcurrent_protection_overcurrent_zone_t,
+    hailo_pipeline_elem_stats_flags_t, hailo_pix_buffer_memory_type_t, hailo_power_mode_t,
+    hailo_scheduling_algorithm_t, hailo_stream_direction_t, hailo_stream_flags_t,
+    hailo_stream_interface_t, hailo_stream_transform_mode_t,
+    hailo_temperature_protection_temperature_zone_t, hailo_vstream_stats_flags_t,
 };
 use crate::handles::hailo_device;
 use crate::status::hailo_status;
@@ -148,6 +157,20 @@ pub struct hailo_vdevice_params_t {
     pub multi_process_service: bool,
 }
 
+impl Default for hailo_vdevice_params_t {
+    /// What `hailo_init_vdevice_params` fills in: one device chosen by the
+    /// runtime, round-robin scheduling, the `UNIQUE` group, no service.
+    fn default() -> Self {
+        Self {
+            device_count: HAILO_DEFAULT_DEVICE_COUNT,
+            device_ids: std::ptr::null_mut(),
+            scheduling_algorithm: HAILO_SCHEDULING_ALGORITHM_ROUND_ROBIN,
+            group_id: HAILO_DEFAULT_VDEVICE_GROUP_ID.as_ptr(),
+            multi_process_service: false,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Data format and quantisation
 // ---------------------------------------------------------------------------
@@ -160,6 +183,17 @@ pub struct hailo_format_t {
     pub flags: hailo_format_flags_t,
 }
 
+impl Default for hailo_format_t {
+    /// `AUTO` type and order with no flags: the runtime picks the HEF's format.
+    fn default() -> Self {
+        Self {
+            type_: HAILO_FORMAT_TYPE_AUTO,
+            order: HAILO_FORMAT_ORDER_AUTO,
+            flags: HAILO_FORMAT_FLAGS_NONE,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct hailo_quant_info_t {
@@ -169,6 +203,23 @@ pub struct hailo_quant_info_t {
     pub limvals_max: float32_t,
 }
 
+impl hailo_quant_info_t {
+    /// Maps a quantised device value back to its real value:
+    /// `(raw - qp_zp) * qp_scale`.
+    pub fn dequantize(&self, raw: float32_t) -> float32_t {
+        (raw - self.qp_zp) * self.qp_scale
+    }
+
+    /// Maps a real value to the device's quantised domain, rounded to the
+    /// nearest step: `round(value / qp_scale + qp_zp)`.
+    ///
+    /// The result is not clamped; cast with `as u8` / `as u16` to saturate
+    /// into the target format type.
+    pub fn quantize(&self, value: float32_t) -> float32_t {
+        (value / self.qp_scale + self.qp_zp).round()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Stream parameters
 // ---------------------------------------------------------------------------
@@ -217,6 +268,7 @@ pub struct hailo_integrated_output_stream_params_t {
 
 /// Anonymous union inside `hailo_stream_parameters_t`.
 #[repr(C)]
+#[derive(Copy, Clone)]
 pub union hailo_stream_params_union_t {
     pub pcie_input_params: hailo_pcie_input_stream_params_t,
     pub integrated_input_params: hailo_integrated_input_stream_params_t,
@@ -225,6 +277,7 @@ pub union hailo_stream_params_union_t {
 }
 
 #[repr(C)]
+#[derive(Copy, Clone)]
 pub struct hailo_stream_parameters_t {
     pub stream_interface: hailo_stream_interface_t,
     pub direction: hailo_stream_direction_t,
@@ -233,6 +286,7 @@ pub struct hailo_stream_parameters_t {
 }
 
 #[repr(C)]
+#[derive(Debug, Copy, Clone)]
 pub struct hailo_stream_parameters_by_name_t {
     pub name: [c_char; HAILO_MAX_STREAM_NAME_SIZE],
     pub stream_params: hailo_stream_parameters_t,
@@ -252,6 +306,20 @@ pub struct hailo_vstream_params_t {
     pub pipeline_elements_stats_flags: hailo_pipeline_elem_stats_flags_t,
 }
 
+impl Default for hailo_vstream_params_t {
+    /// Auto user format, `HAILO_DEFAULT_VSTREAM_TIMEOUT_MS`,
+    /// `HAILO_DEFAULT_VSTREAM_QUEUE_SIZE` and no statistics.
+    fn default() -> Self {
+        Self {
+            user_buffer_format: hailo_format_t::default(),
+            timeout_ms: HAILO_DEFAULT_VSTREAM_TIMEOUT_MS,
+            queue_size: HAILO_DEFAULT_VSTREAM_QUEUE_SIZE,
+            vstream_stats_flags: HAILO_VSTREAM_STATS_NONE,
+            pipeline_elements_stats_flags: HAILO_PIPELINE_ELEM_STATS_NONE,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct hailo_input_vstream_params_by_name_t {
@@ -718,6 +786,15 @@ pub struct hailo_network_parameters_t {
     pub batch_size: u16,
 }
 
+impl Default for hailo_network_parameters_t {
+    /// `HAILO_DEFAULT_BATCH_SIZE`, letting the runtime choose.
+    fn default() -> Self {
+        Self {
+            batch_size: HAILO_DEFAULT_BATCH_SIZE,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct hailo_network_parameters_by_name_t {
@@ -820,8 +897,40 @@ pub struct hailo_i2c_slave_config_t {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::enums::HAILO_NOTIFICATION_ID_HW_INFER_MANAGER_INFER_DONE;
     use std::mem::{align_of, offset_of, size_of};
 
+    // --- Debug and Default ---------------------------------------------------
+
+    #[test]
+    fn defaults_mirror_header_constants() {
+        let params = hailo_vstream_params_t::default();
+        assert_eq!(params.timeout_ms, HAILO_DEFAULT_VSTREAM_TIMEOUT_MS);
+        assert_eq!(params.queue_size, HAILO_DEFAULT_VSTREAM_QUEUE_SIZE);
+        assert_eq!(params.user_buffer_format.order, HAILO_FORMAT_ORDER_AUTO);
+        assert_eq!(params.vstream_stats_flags, HAILO_VSTREAM_STATS_NONE);
+        assert_eq!(
+            hailo_network_parameters_t::default().batch_size,
+            HAILO_DEFAULT_BATCH_SIZE
+        );
+        let vdevice = hailo_vdevice_params_t::default();
+        assert_eq!(vdevice.device_count, HAILO_DEFAULT_DEVICE_COUNT);
+        assert!(vdevice.device_ids.is_null());
+        assert_eq!(
+            unsafe { std::ffi::CStr::from_ptr(vdevice.group_id) },
+            HAILO_DEFAULT_VDEVICE_GROUP_ID
+        );
+    }
+
+    #[test]
+    fn debug_decodes_notifications() {
+        // SAFETY: all-zero is valid for this plain-data struct.
+        let mut notification: hailo_notification_t = unsafe { std::mem::zeroed() };
+        notification.id = HAILO_NOTIFICATION_ID_HW_INFER_MANAGER_INFER_DONE;
+        notification.body.hw_infer_manager_infer_done_notification.infer_cycles = 42;
+        assert!(format!("{notification:?}").contains("InferDone { infer_cycles: 42 }"));
+    }
+
     // --- Primitive type aliases ----------------------------------------------
 
     #[test]
@@ -1036,6 +1145,22 @@ mod tests {
         assert_eq!(offset_of!(hailo_quant_info_t, limvals_max), 12);
     }
 
+    #[test]
+    fn hailo_quant_info_t_round_trips() {
+        let qi = hailo_quant_info_t {
+            qp_zp: 128.0,
+            qp_scale: 0.5,
+            limvals_min: -64.0,
+            limvals_max: 63.5,
+        };
+        assert_eq!(qi.dequantize(128.0), 0.0);
+        assert_eq!(qi.dequantize(130.0), 1.0);
+        assert_eq!(qi.quantize(1.0), 130.0);
+        assert_eq!(qi.quantize(1.2), 130.0);
+        assert_eq!(qi.quantize(qi.dequantize(77.0)), 77.0);
+        assert_eq!(qi.quantize(1000.0) as u8, u8::MAX);
+    }
+
     // --- Stream params -------------------------------------------------------
 
     #[test]
@@ -0,0 +1,101 @@
+//! Crate-private helpers shared by the safe wrappers.
+
+use std::ffi::CString;
+use std::path::Path;
+
+use crate::error::{HailoError, HailoResult, check};
+use crate::status::{HAILO_INSUFFICIENT_BUFFER, hailo_status};
+
+/// Converts a name argument for the C API, rejecting interior NULs.
+pub(crate) fn c_string(s: &str) -> HailoResult<CString> {
+    CString::new(s).map_err(|_| HailoError::InvalidArgument)
+}
+
+/// Converts a path argument for the C API.  On Unix the raw bytes are
+/// passed through, so non-UTF-8 paths work; elsewhere the path must be
+/// UTF-8.  Interior NULs are rejected.
+pub(crate) fn path_c_string(path: &Path) -> HailoResult<CString> {
+    #[cfg(unix)]
+    let bytes = std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str());
+    #[cfg(not(unix))]
+    let bytes = path.to_str().ok_or(HailoError::InvalidArgument)?;
+    CString::new(bytes).map_err(|_| HailoError::InvalidArgument)
+}
+
+/// Runs the C "in/out length" array protocol until the buffer is big enough.
+///
+/// `fill` receives a pointer to `*len` writable elements and must set `*len`
+/// to the number of elements written (or, on `HAILO_INSUFFICIENT_BUFFER`, to
+/// the number required).
+pub(crate) fn fetch_array<T>(
+    initial_capacity: usize,
+    mut fill: impl FnMut(*mut T, &mut usize) -> hailo_status,
+) -> HailoResult<Vec<T>> {
+    let mut capacity = initial_capacity.max(1);
+    loop {
+        let mut items = Vec::with_capacity(capacity);
+        let mut len = capacity;
+        let status = fill(items.as_mut_ptr(), &mut len);
+        if status == HAILO_INSUFFICIENT_BUFFER {
+            capacity = len.max(capacity * 2);
+            continue;
+        }
+        check(status)?;
+        if len > capacity {
+            return Err(HailoError::InternalFailure);
+        }
+        // SAFETY: the call succeeded and reported `len <= capacity` elements
+        // written to the front of the buffer.
+        unsafe { items.set_len(len) };
+        return Ok(items);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::{HAILO_INVALID_ARGUMENT, HAILO_SUCCESS};
+
+    #[test]
+    #[cfg(unix)]
+    fn paths_keep_non_utf8_bytes() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"models/\xffnet.hef"));
+        assert_eq!(
+            path_c_string(path).unwrap().as_bytes(),
+            b"models/\xffnet.hef"
+        );
+        assert_eq!(
+            path_c_string(Path::new("a\0b")).unwrap_err(),
+            HailoError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn fetch_array_retries_on_insufficient_buffer() {
+        let mut calls = 0;
+        let items = fetch_array::<u32>(2, |ptr, len| {
+            calls += 1;
+            if *len < 5 {
+                *len = 5;
+                return HAILO_INSUFFICIENT_BUFFER;
+            }
+            for i in 0..5 {
+                unsafe { ptr.add(i).write(i as u32) };
+            }
+            *len = 5;
+            HAILO_SUCCESS
+        })
+        .unwrap();
+        assert_eq!(items, [0, 1, 2, 3, 4]);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn fetch_array_propagates_errors() {
+        let err = fetch_array::<u8>(4, |_, _| HAILO_INVALID_ARGUMENT).unwrap_err();
+        assert_eq!(err, HailoError::InvalidArgument);
+    }
+}
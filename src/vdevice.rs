@@ -0,0 +1,256 @@
+//! Safe wrapper around `hailo_vdevice` and its creation parameters.
+//!
+//! `hailo_vdevice_params_t` points at caller-owned device ids and a group id
+//! string.  [`VDeviceBuilder`] owns that storage and keeps it alive for the
+//! duration of `hailo_create_vdevice`.
+
+use std::ffi::CString;
+use std::mem::MaybeUninit;
+use std::ptr;
+
+use crate::device::{DeviceId, DeviceRef};
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{
+    hailo_create_vdevice, hailo_get_physical_devices, hailo_init_vdevice_params,
+    hailo_release_vdevice, hailo_vdevice_get_physical_devices_ids,
+};
+use crate::handles::{hailo_device, hailo_vdevice};
+use crate::typed_enums::SchedulingAlgorithm;
+use crate::types::{hailo_device_id_t, hailo_vdevice_params_t};
+use crate::util::{c_string, fetch_array};
+
+/// Initial capacity for the physical-device queries.
+const PHYSICAL_DEVICES_CAPACITY: usize = 4;
+
+/// Builder for [`VDevice`].  Unset fields keep the `hailo_init_vdevice_params`
+/// defaults.
+#[derive(Debug, Clone, Default)]
+pub struct VDeviceBuilder {
+    device_count: Option<u32>,
+    device_ids: Option<Vec<DeviceId>>,
+    scheduling_algorithm: Option<SchedulingAlgorithm>,
+    group_id: Option<String>,
+    multi_process_service: Option<bool>,
+}
+
+/// Backing storage referenced by a filled-in `hailo_vdevice_params_t`.
+struct ParamsStorage {
+    device_ids: Vec<hailo_device_id_t>,
+    group_id: Option<CString>,
+}
+
+impl VDeviceBuilder {
+    /// Number of devices to aggregate when no explicit ids are given.
+    pub fn device_count(mut self, count: u32) -> Self {
+        self.device_count = Some(count);
+        self
+    }
+
+    /// Explicit devices to aggregate; implies the device count.
+    pub fn device_ids(mut self, ids: impl IntoIterator<Item = DeviceId>) -> Self {
+        self.device_ids = Some(ids.into_iter().collect());
+        self
+    }
+
+    pub fn scheduling_algorithm(mut self, algorithm: SchedulingAlgorithm) -> Self {
+        self.scheduling_algorithm = Some(algorithm);
+        self
+    }
+
+    /// Group id shared by processes that should use the same devices.
+    pub fn group_id(mut self, group_id: impl Into<String>) -> Self {
+        self.group_id = Some(group_id.into());
+        self
+    }
+
+    pub fn multi_process_service(mut self, enabled: bool) -> Self {
+        self.multi_process_service = Some(enabled);
+        self
+    }
+
+    /// Overlays the builder onto `params`.  The pointers written into
+    /// `params` borrow from the returned storage.
+    fn apply(&self, params: &mut hailo_vdevice_params_t) -> HailoResult<ParamsStorage> {
+        let mut storage = ParamsStorage {
+            device_ids: Vec::new(),
+            group_id: None,
+        };
+
+        if let Some(count) = self.device_count {
+            params.device_count = count;
+        }
+        if let Some(ids) = &self.device_ids {
+            let count = u32::try_from(ids.len()).map_err(|_| HailoError::InvalidArgument)?;
+            if ids.is_empty() || self.device_count.is_some_and(|c| c != count) {
+                return Err(HailoError::InvalidArgument);
+            }
+            storage.device_ids = ids
+                .iter()
+                .map(DeviceId::to_raw)
+                .collect::<HailoResult<_>>()?;
+            params.device_count = count;
+            params.device_ids = storage.device_ids.as_mut_ptr();
+        }
+        if let Some(algorithm) = self.scheduling_algorithm {
+            params.scheduling_algorithm = algorithm.into();
+        }
+        if let Some(group_id) = &self.group_id {
+            let group_id = c_string(group_id)?;
+            params.group_id = group_id.as_ptr();
+            storage.group_id = Some(group_id);
+        }
+        if let Some(enabled) = self.multi_process_service {
+            params.multi_process_service = enabled;
+        }
+        Ok(storage)
+    }
+
+    /// Creates the virtual device (`hailo_create_vdevice`).
+    pub fn build(&self) -> HailoResult<VDevice> {
+        let mut params = MaybeUninit::<hailo_vdevice_params_t>::uninit();
+        check(unsafe { hailo_init_vdevice_params(params.as_mut_ptr()) })?;
+        let mut params = unsafe { params.assume_init() };
+        let storage = self.apply(&mut params)?;
+
+        let mut raw = ptr::null_mut();
+        check(unsafe { hailo_create_vdevice(&mut params, &mut raw) })?;
+        drop(storage);
+        Ok(VDevice { raw })
+    }
+}
+
+/// Owned virtual device.  Released on drop.
+///
+/// `Send` and `Sync`, like [`Device`](crate::Device): configuring and
+/// querying are internally locked by HailoRT.
+#[derive(Debug)]
+pub struct VDevice {
+    raw: hailo_vdevice,
+}
+
+// SAFETY: HailoRT guards vdevice state with its own mutex.
+unsafe impl Send for VDevice {}
+// SAFETY: as above.
+unsafe impl Sync for VDevice {}
+
+impl VDevice {
+    pub fn builder() -> VDeviceBuilder {
+        VDeviceBuilder::default()
+    }
+
+    /// Takes ownership of a handle created elsewhere.
+    ///
+    /// # Safety
+    /// `raw` must be a valid vdevice handle that nothing else releases.
+    pub unsafe fn from_raw(raw: hailo_vdevice) -> Self {
+        Self { raw }
+    }
+
+    pub fn as_raw(&self) -> hailo_vdevice {
+        self.raw
+    }
+
+    /// Gives up ownership; the caller must call `hailo_release_vdevice`.
+    pub fn into_raw(self) -> hailo_vdevice {
+        let raw = self.raw;
+        std::mem::forget(self);
+        raw
+    }
+
+    /// The physical devices behind this vdevice.  They are owned by the
+    /// vdevice and stay valid only while it is alive.
+    pub fn physical_devices(&self) -> HailoResult<Vec<DeviceRef<'_>>> {
+        let raw = fetch_array::<hailo_device>(PHYSICAL_DEVICES_CAPACITY, |ptr, len| unsafe {
+            hailo_get_physical_devices(self.raw, ptr, len)
+        })?;
+        Ok(raw
+            .into_iter()
+            .map(|d| unsafe { DeviceRef::new(d) })
+            .collect())
+    }
+
+    /// Ids of the physical devices behind this vdevice.
+    pub fn physical_device_ids(&self) -> HailoResult<Vec<DeviceId>> {
+        let ids = fetch_array::<hailo_device_id_t>(PHYSICAL_DEVICES_CAPACITY, |ptr, len| unsafe {
+            hailo_vdevice_get_physical_devices_ids(self.raw, ptr, len)
+        })?;
+        Ok(ids.iter().map(DeviceId::from).collect())
+    }
+}
+
+impl Drop for VDevice {
+    fn drop(&mut self) {
+        unsafe { hailo_release_vdevice(self.raw) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::HAILO_DEFAULT_VDEVICE_GROUP_ID;
+    use crate::enums::{HAILO_SCHEDULING_ALGORITHM_NONE, HAILO_SCHEDULING_ALGORITHM_ROUND_ROBIN};
+    use std::ffi::CStr;
+
+    #[test]
+    fn empty_builder_keeps_defaults() {
+        let mut params = hailo_vdevice_params_t::default();
+        VDevice::builder().apply(&mut params).unwrap();
+        assert_eq!(params.device_count, 1);
+        assert!(params.device_ids.is_null());
+        assert_eq!(
+            unsafe { CStr::from_ptr(params.group_id) },
+            HAILO_DEFAULT_VDEVICE_GROUP_ID
+        );
+        assert_eq!(
+            params.scheduling_algorithm,
+            HAILO_SCHEDULING_ALGORITHM_ROUND_ROBIN
+        );
+    }
+
+    #[test]
+    fn storage_backs_params_pointers() {
+        let ids = ["0000:01:00.0", "0000:02:00.0"].map(|s| DeviceId::new(s).unwrap());
+        let builder = VDevice::builder()
+            .device_ids(ids.clone())
+            .group_id("shared")
+            .scheduling_algorithm(SchedulingAlgorithm::None)
+            .multi_process_service(true);
+        let mut params = hailo_vdevice_params_t::default();
+        let storage = builder.apply(&mut params).unwrap();
+
+        assert_eq!(params.device_count, 2);
+        let raw = unsafe { std::slice::from_raw_parts(params.device_ids, 2) };
+        assert_eq!(DeviceId::from(&raw[1]), ids[1]);
+        assert_eq!(
+            unsafe { CStr::from_ptr(params.group_id) }.to_str(),
+            Ok("shared")
+        );
+        assert_eq!(params.scheduling_algorithm, HAILO_SCHEDULING_ALGORITHM_NONE);
+        assert!(params.multi_process_service);
+        drop(storage);
+    }
+
+    #[test]
+    fn invalid_combinations_are_rejected() {
+        let id = DeviceId::new("0000:01:00.0").unwrap();
+        let mismatched = VDevice::builder().device_count(2).device_ids([id]);
+        assert_eq!(
+            mismatched
+                .apply(&mut hailo_vdevice_params_t::default())
+                .err(),
+            Some(HailoError::InvalidArgument)
+        );
+
+        let empty = VDevice::builder().device_ids([]);
+        assert_eq!(
+            empty.apply(&mut hailo_vdevice_params_t::default()).err(),
+            Some(HailoError::InvalidArgument)
+        );
+
+        let nul = VDevice::builder().group_id("a\0b");
+        assert_eq!(
+            nul.apply(&mut hailo_vdevice_params_t::default()).err(),
+            Some(HailoError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Typed input/output vstreams.
+//!
+//! A vstream performs host-side transformations (quantisation, reordering)
+//! so frames are read and written in the user buffer format.  The element
+//! type of every read or write is checked against that format, and the
+//! buffer length against the frame size, before anything reaches C.
+
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::mem::{MaybeUninit, size_of_val};
+use std::ptr;
+
+use crate::constants::HAILO_MAX_STREAMS_COUNT;
+use crate::error::{HailoError, HailoResult, check};
+use crate::ffi::{
+    hailo_create_input_vstreams, hailo_create_output_vstreams, hailo_get_input_vstream_frame_size,
+    hailo_get_input_vstream_user_format, hailo_get_output_vstream_frame_size,
+    hailo_get_output_vstream_user_format, hailo_input_vstream_clear, hailo_input_vstream_flush,
+    hailo_input_vstream_get_info, hailo_make_input_vstream_params,
+    hailo_make_output_vstream_params, hailo_output_vstream_get_info, hailo_release_input_vstreams,
+    hailo_release_output_vstreams, hailo_vstream_read_raw_buffer, hailo_vstream_write_raw_buffer,
+};
+use crate::handles::{hailo_input_vstream, hailo_output_vstream};
+use crate::hef::VStreamInfo;
+use crate::network_group::ConfiguredNetworkGroup;
+use crate::typed_enums::{Format, FormatType};
+use crate::types::{
+    hailo_format_t, hailo_input_vstream_params_by_name_t, hailo_output_vstream_params_by_name_t,
+};
+use crate::util::fetch_array;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Element types a vstream frame can be read or written as.
+pub trait FrameElement: Copy + sealed::Sealed {
+    /// The `user_buffer_format.type_` this element corresponds to.
+    const FORMAT_TYPE: FormatType;
+}
+
+macro_rules! frame_elements {
+    ($($ty:ty => $format:ident,)*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+            impl FrameElement for $ty {
+                const FORMAT_TYPE: FormatType = FormatType::$format;
+            }
+        )*
+    };
+}
+
+frame_elements! {
+    u8 => Uint8,
+    u16 => Uint16,
+    f32 => Float32,
+}
+
+/// Fails with `InvalidArgument` unless `frame` is exactly one frame of the
+/// vstream's user format type.
+fn check_frame<T: FrameElement>(
+    format_type: FormatType,
+    frame_size: usize,
+    frame: &[T],
+) -> HailoResult<()> {
+    if T::FORMAT_TYPE != format_type || size_of_val(frame) != frame_size {
+        return Err(HailoError::InvalidArgument);
+    }
+    Ok(())
+}
+
+impl ConfiguredNetworkGroup<'_> {
+    /// Default input vstream params for every input, with user buffers of
+    /// `format_type` (`hailo_make_input_vstream_params`).
+    pub fn input_vstream_params(
+        &self,
+        format_type: FormatType,
+    ) -> HailoResult<Vec<hailo_input_vstream_params_by_name_t>> {
+        fetch_array(HAILO_MAX_STREAMS_COUNT, |ptr, len| unsafe {
+            hailo_make_input_vstream_params(self.as_raw(), false, format_type.into(), ptr, len)
+        })
+    }
+
+    /// Default output vstream params for every output, with user buffers of
+    /// `format_type` (`hailo_make_output_vstream_params`).
+    pub fn output_vstream_params(
+        &self,
+        format_type: FormatType,
+    ) -> HailoResult<Vec<hailo_output_vstream_params_by_name_t>> {
+        fetch_array(HAILO_MAX_STREAMS_COUNT, |ptr, len| unsafe {
+            hailo_make_output_vstream_params(self.as_raw(), false, format_type.into(), ptr, len)
+        })
+    }
+
+    /// Creates one input vstream per entry of `params`, in order.
+    pub fn create_input_vstreams(
+        &self,
+        params: &[hailo_input_vstream_params_by_name_t],
+    ) -> HailoResult<Vec<InputVStream<'_>>> {
+        let mut raw = vec![ptr::null_mut(); params.len()];
+        check(unsafe {
+            hailo_create_input_vstreams(
+                self.as_raw(),
+                params.as_ptr(),
+                params.len(),
+                raw.as_mut_ptr(),
+            )
+        })?;
+        // Wrap everything first so an error below still releases each handle.
+        let mut vstreams: Vec<_> = raw
+            .into_iter()
+            .map(|raw| InputVStream {
+                raw,
+                frame_size: 0,
+                user_format: Format::default(),
+                _group: PhantomData,
+            })
+            .collect();
+        for vstream in &mut vstreams {
+            check(unsafe {
+                hailo_get_input_vstream_frame_size(vstream.raw, &mut vstream.frame_size)
+            })?;
+            let mut format = hailo_format_t::default();
+            check(unsafe { hailo_get_input_vstream_user_format(vstream.raw, &mut format) })?;
+            vstream.user_format = format.into();
+        }
+        Ok(vstreams)
+    }
+
+    /// Creates one output vstream per entry of `params`, in order.
+    pub fn create_output_vstreams(
+        &self,
+        params: &[hailo_output_vstream_params_by_name_t],
+    ) -> HailoResult<Vec<OutputVStream<'_>>> {
+        let mut raw = vec![ptr::null_mut(); params.len()];
+        check(unsafe {
+            hailo_create_output_vstreams(
+                self.as_raw(),
+                params.as_ptr(),
+                params.len(),
+                raw.as_mut_ptr(),
+            )
+        })?;
+        let mut vstreams: Vec<_> = raw
+            .into_iter()
+            .map(|raw| OutputVStream {
+                raw,
+                frame_size: 0,
+                user_format: Format::default(),
+                _group: PhantomData,
+            })
+            .collect();
+        for vstream in &mut vstreams {
+            check(unsafe {
+                hailo_get_output_vstream_frame_size(vstream.raw, &mut vstream.frame_size)
+            })?;
+            let mut format = hailo_format_t::default();
+            check(unsafe { hailo_get_output_vstream_user_format(vstream.raw, &mut format) })?;
+            vstream.user_format = format.into();
+        }
+        Ok(vstreams)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Input
+// ---------------------------------------------------------------------------
+
+/// Input vstream.  Borrows its network group; released on drop.
+///
+/// `Send` but not `Sync`: move it to a dedicated writer thread, one per
+/// vstream; HailoRT does not support concurrent writes on one vstream.
+#[derive(Debug)]
+pub struct InputVStream<'a> {
+    raw: hailo_input_vstream,
+    frame_size: usize,
+    user_format: Format,
+    _group: PhantomData<&'a ()>,
+}
+
+// SAFETY: vstream handles are not tied to the creating thread.
+unsafe impl Send for InputVStream<'_> {}
+
+impl InputVStream<'_> {
+    pub fn as_raw(&self) -> hailo_input_vstream {
+        self.raw
+    }
+
+    pub fn info(&self) -> HailoResult<VStreamInfo> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_input_vstream_get_info(self.raw, raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(VStreamInfo::from(&raw))
+    }
+
+    /// Size in bytes of one frame in the user buffer format.
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    pub fn user_format(&self) -> Format {
+        self.user_format
+    }
+
+    /// Writes one frame.  `T` must match the user format type and `frame`
+    /// must be exactly [`frame_size`](Self::frame_size) bytes.
+    pub fn write<T: FrameElement>(&self, frame: &[T]) -> HailoResult<()> {
+        check_frame(self.user_format.format_type, self.frame_size, frame)?;
+        check(unsafe {
+            hailo_vstream_write_raw_buffer(
+                self.raw,
+                frame.as_ptr().cast::<c_void>(),
+                size_of_val(frame),
+            )
+        })
+    }
+
+    /// Blocks until all written frames have been sent to the device.
+    pub fn flush(&self) -> HailoResult<()> {
+        check(unsafe { hailo_input_vstream_flush(self.raw) })
+    }
+
+    /// Drops frames queued but not yet sent.
+    pub fn clear(&self) -> HailoResult<()> {
+        check(unsafe { hailo_input_vstream_clear(self.raw) })
+    }
+}
+
+impl Drop for InputVStream<'_> {
+    fn drop(&mut self) {
+        unsafe { hailo_release_input_vstreams(&self.raw, 1) };
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Output
+// ---------------------------------------------------------------------------
+
+/// Output vstream.  Borrows its network group; released on drop.
+///
+/// `Send` but not `Sync`: move it to a dedicated reader thread, one per
+/// vstream; HailoRT does not support concurrent reads on one vstream.
+#[derive(Debug)]
+pub struct OutputVStream<'a> {
+    raw: hailo_output_vstream,
+    frame_size: usize,
+    user_format: Format,
+    _group: PhantomData<&'a ()>,
+}
+
+// SAFETY: vstream handles are not tied to the creating thread.
+unsafe impl Send for OutputVStream<'_> {}
+
+impl OutputVStream<'_> {
+    pub fn as_raw(&self) -> hailo_output_vstream {
+        self.raw
+    }
+
+    pub fn info(&self) -> HailoResult<VStreamInfo> {
+        let mut raw = MaybeUninit::uninit();
+        check(unsafe { hailo_output_vstream_get_info(self.raw, raw.as_mut_ptr()) })?;
+        let raw = unsafe { raw.assume_init() };
+        Ok(VStreamInfo::from(&raw))
+    }
+
+    /// Size in bytes of one frame in the user buffer format.
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    pub fn user_format(&self) -> Format {
+        self.user_format
+    }
+
+    /// Reads one frame into `frame`.  `T` must match the user format type
+    /// and `frame` must be exactly [`frame_size`](Self::frame_size) bytes.
+    pub fn read_into<T: FrameElement>(&self, frame: &mut [T]) -> HailoResult<()> {
+        check_frame(self.user_format.format_type, self.frame_size, frame)?;
+        check(unsafe {
+            hailo_vstream_read_raw_buffer(
+                self.raw,
+                frame.as_mut_ptr().cast::<c_void>(),
+                size_of_val(frame),
+            )
+        })
+    }
+}
+
+impl Drop for OutputVStream<'_> {
+    fn drop(&mut self) {
+        unsafe { hailo_release_output_vstreams(&self.raw, 1) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn element_type_must_match_format() {
+        assert!(check_frame(FormatType::Uint8, 4, &[0u8; 4]).is_ok());
+        assert!(check_frame(FormatType::Uint16, 8, &[0u16; 4]).is_ok());
+        assert!(check_frame(FormatType::Float32, 16, &[0f32; 4]).is_ok());
+        assert_eq!(
+            check_frame(FormatType::Float32, 4, &[0u8; 4]),
+            Err(HailoError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn frame_size_is_checked_in_bytes() {
+        assert_eq!(
+            check_frame(FormatType::Uint16, 8, &[0u16; 3]),
+            Err(HailoError::InvalidArgument)
+        );
+        assert_eq!(
+            check_frame(FormatType::Float32, 16, &[0f32; 16]),
+            Err(HailoError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn streams_are_send() {
+        fn send<T: Send>() {}
+        send::<InputVStream<'_>>();
+        send::<OutputVStream<'_>>();
+        send::<crate::InputStream<'_>>();
+        send::<crate::OutputStream<'_>>();
+    }
+}
@@ -0,0 +1,179 @@
+//! Firmware watchdog control and previous-run system state.
+//!
+//! [`Device::watchdog`] returns a [`Watchdog`] for one CPU wrapping
+//! `hailo_wd_enable`, `hailo_wd_disable` and `hailo_wd_config`.
+//! [`Device::previous_system_state`] reads the state word the firmware kept
+//! from the run before the last reset.
+//!
+//! Neither `hailort.h` nor the HailoRT documentation defines that word's bit
+//! layout, so this crate cannot tell from it alone whether the previous run
+//! ended in a watchdog reset, and deliberately offers no
+//! `was_watchdog_reset()` that would guess.  [`PreviousSystemState`] exposes
+//! the set bits for logging and compares readings against a reference taken
+//! after a known clean boot; a reading that differs marks an abnormal end of
+//! the previous run, of which a watchdog reset is one cause.
+
+use std::fmt;
+
+use crate::device::Device;
+use crate::enums::*;
+use crate::error::{HailoResult, check};
+use crate::ffi::{
+    hailo_get_previous_system_state, hailo_wd_config, hailo_wd_disable, hailo_wd_enable,
+};
+use crate::typed_enums::c_enum;
+
+c_enum! {
+    /// Firmware CPU (`hailo_cpu_id_t`).
+    pub enum CpuId: hailo_cpu_id_t {
+        /// `HAILO_CPU_ID_0`, the app CPU.
+        Cpu0 = HAILO_CPU_ID_0 => "cpu0",
+        /// `HAILO_CPU_ID_1`, the core CPU.
+        Cpu1 = HAILO_CPU_ID_1 => "cpu1",
+    }
+}
+
+c_enum! {
+    /// What the watchdog supervises (`hailo_watchdog_mode_t`).
+    pub enum WatchdogMode: hailo_watchdog_mode_t {
+        /// Hardware timer kicked by the firmware's software tasks.
+        HwSw = HAILO_WATCHDOG_MODE_HW_SW => "hw_sw",
+        /// Hardware timer only.
+        HwOnly = HAILO_WATCHDOG_MODE_HW_ONLY => "hw_only",
+    }
+}
+
+/// Watchdog of one firmware CPU.
+#[derive(Debug, Copy, Clone)]
+pub struct Watchdog<'d> {
+    device: &'d Device,
+    cpu: CpuId,
+}
+
+impl Watchdog<'_> {
+    pub fn cpu(&self) -> CpuId {
+        self.cpu
+    }
+
+    pub fn enable(&self) -> HailoResult<()> {
+        check(unsafe { hailo_wd_enable(self.device.as_raw(), self.cpu.into()) })
+    }
+
+    pub fn disable(&self) -> HailoResult<()> {
+        check(unsafe { hailo_wd_disable(self.device.as_raw(), self.cpu.into()) })
+    }
+
+    /// Sets the timeout, in watchdog clock cycles, and the supervision mode.
+    pub fn configure(&self, cycles: u32, mode: WatchdogMode) -> HailoResult<()> {
+        check(unsafe {
+            hailo_wd_config(self.device.as_raw(), self.cpu.into(), cycles, mode.into())
+        })
+    }
+}
+
+/// State word the firmware preserved from the previous run of one CPU
+/// (`hailo_get_previous_system_state`).
+///
+/// The bit layout is undocumented upstream (see the [module docs](self)), so
+/// the word is kept verbatim and only split into its set bits.  A fleet
+/// agent can record the value after a clean boot and compare later readings
+/// against it with [`differs_from`](Self::differs_from).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PreviousSystemState {
+    pub cpu: CpuId,
+    pub raw: u32,
+}
+
+impl PreviousSystemState {
+    /// Whether this reading differs from a reference taken on the same CPU.
+    pub fn differs_from(&self, reference: &Self) -> bool {
+        self.cpu == reference.cpu && self.raw != reference.raw
+    }
+
+    /// Indices of the set bits, lowest first.
+    pub fn set_bits(&self) -> impl Iterator<Item = u32> + use<> {
+        let raw = self.raw;
+        (0..u32::BITS).filter(move |bit| raw & (1 << bit) != 0)
+    }
+
+    /// Bits set here but not in `reference`, e.g. a clean-boot reading.
+    pub fn bits_added_since(&self, reference: &Self) -> impl Iterator<Item = u32> + use<> {
+        PreviousSystemState {
+            cpu: self.cpu,
+            raw: self.raw & !reference.raw,
+        }
+        .set_bits()
+    }
+}
+
+impl fmt::Display for PreviousSystemState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:#010x}", self.cpu, self.raw)?;
+        let mut bits = self.set_bits().peekable();
+        if bits.peek().is_some() {
+            f.write_str(" (bits")?;
+            for (i, bit) in bits.enumerate() {
+                write!(f, "{}{bit}", if i == 0 { " " } else { ", " })?;
+            }
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+impl Device {
+    pub fn watchdog(&self, cpu: CpuId) -> Watchdog<'_> {
+        Watchdog { device: self, cpu }
+    }
+
+    pub fn previous_system_state(&self, cpu: CpuId) -> HailoResult<PreviousSystemState> {
+        let mut raw = 0;
+        check(unsafe { hailo_get_previous_system_state(self.as_raw(), cpu.into(), &mut raw) })?;
+        Ok(PreviousSystemState { cpu, raw })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_and_modes_map_to_c_values() {
+        assert_eq!(hailo_cpu_id_t::from(CpuId::Cpu1), HAILO_CPU_ID_1);
+        assert_eq!(CpuId::from(0), CpuId::Cpu0);
+        assert_eq!(CpuId::from(2), CpuId::Unknown(2));
+        assert_eq!(CpuId::Unknown(2).to_string(), "unknown (2)");
+        assert_eq!(
+            hailo_watchdog_mode_t::from(WatchdogMode::HwOnly),
+            HAILO_WATCHDOG_MODE_HW_ONLY
+        );
+    }
+
+    #[test]
+    fn system_state_compares_per_cpu() {
+        let clean = PreviousSystemState {
+            cpu: CpuId::Cpu0,
+            raw: 0,
+        };
+        let after = PreviousSystemState {
+            cpu: CpuId::Cpu0,
+            raw: 0x10,
+        };
+        let other_cpu = PreviousSystemState {
+            cpu: CpuId::Cpu1,
+            raw: 0x10,
+        };
+        assert!(after.differs_from(&clean));
+        assert!(!other_cpu.differs_from(&clean));
+        assert_eq!(clean.to_string(), "cpu0: 0x00000000");
+        assert_eq!(after.to_string(), "cpu0: 0x00000010 (bits 4)");
+
+        let both = PreviousSystemState {
+            cpu: CpuId::Cpu0,
+            raw: 0x8000_0011,
+        };
+        assert_eq!(both.set_bits().collect::<Vec<_>>(), [0, 4, 31]);
+        assert_eq!(both.bits_added_since(&after).collect::<Vec<_>>(), [0, 31]);
+        assert_eq!(both.to_string(), "cpu0: 0x80000011 (bits 0, 4, 31)");
+    }
+}